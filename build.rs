@@ -0,0 +1,27 @@
+//! Detects the version of the compiler, so as to use (min) const generics,
+//! stable since `1.51.0`, whenever available (without extra dependencies).
+
+use ::std::{env, process::Command};
+
+fn main ()
+{
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rustc-check-cfg=cfg(stackbox_const_generics)");
+    if rustc_minor_version().unwrap_or(0) >= 51 {
+        println!("cargo:rustc-cfg=stackbox_const_generics");
+    }
+}
+
+fn rustc_minor_version ()
+  -> Option<u32>
+{
+    let rustc = env::var_os("RUSTC")?;
+    let output = Command::new(rustc).arg("--version").output().ok()?;
+    let version = String::from_utf8(output.stdout).ok()?;
+    // e.g., `rustc 1.42.0 (b8cedc004 2020-03-09)`
+    let mut pieces = version.split('.');
+    if pieces.next() != Some("rustc 1") {
+        return None;
+    }
+    pieces.next()?.parse().ok()
+}
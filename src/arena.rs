@@ -0,0 +1,291 @@
+use crate::prelude::*;
+
+use ::core::{
+    cell::{Cell, UnsafeCell},
+    mem::{self, MaybeUninit},
+    ptr,
+};
+
+/// A bump arena of `N` bytes, living in the current frame, out of which
+/// [`StackBox`]es of heterogeneous types can be obtained.
+///
+/// Contrary to a [`Slot`], which can only hold one value of one type, a
+/// `StackArena` allocates through a shared reference, so any number of
+/// values (of any type) can be moved into it, as long as they fit: all the
+/// so-obtained `StackBox`es share the lifetime of that borrow of the arena.
+///
+/// Alignment padding is accounted for, so the number of bytes actually
+/// available for a given value may be smaller than the
+/// [`.remaining()`][`StackArena::remaining`] capacity.
+///
+/// Values are dropped by their owning `StackBox`es: the arena itself never
+/// drops anything, and leaked values thus stay leaked.
+///
+/// [`Slot`]: `crate::Slot`
+///
+/// ## Example
+///
+/// ```rust
+/// use ::stackbox::prelude::*;
+/// use ::stackbox::StackArena;
+///
+/// let arena = StackArena::<128>::new();
+/// let answer: StackBox<'_, i32> = arena.stackbox(42).unwrap();
+/// let greeting: StackBox<'_, str> = arena.copy_str("Hello").unwrap();
+/// let names: StackBox<'_, [String]> =
+///     arena.uninit_slice(2).unwrap().init_from_fn(|i| format!("#{}", i))
+/// ;
+/// assert_eq!(*answer, 42);
+/// assert_eq!(&*greeting, "Hello");
+/// assert_eq!(*names, ["#0", "#1"]);
+/// assert!(arena.stackbox([0_u8; 128]).is_none());
+/// ```
+pub
+struct StackArena<const N: usize> {
+    buf: UnsafeCell<[MaybeUninit<u8>; N]>,
+    /// Invariant: `used <= N`, and the bytes past `used` are not borrowed.
+    used: Cell<usize>,
+}
+
+impl<const N: usize> StackArena<N> {
+    /// An empty arena.
+    #[inline]
+    pub
+    const
+    fn new ()
+      -> StackArena<N>
+    {
+        StackArena {
+            buf: UnsafeCell::new([MaybeUninit::uninit(); N]),
+            used: Cell::new(0),
+        }
+    }
+
+    /// The `N` in `StackArena<N>`.
+    #[inline]
+    pub
+    fn capacity (self: &'_ StackArena<N>)
+      -> usize
+    {
+        N
+    }
+
+    /// How many bytes have not been allocated yet (some of which may end up
+    /// used as alignment padding).
+    #[inline]
+    pub
+    fn remaining (self: &'_ StackArena<N>)
+      -> usize
+    {
+        N - self.used.get()
+    }
+
+    /// Makes the whole capacity available again.
+    ///
+    /// The exclusive borrow guarantees that no `StackBox` obtained from the
+    /// arena is still alive.
+    #[inline]
+    pub
+    fn reset (self: &'_ mut StackArena<N>)
+    {
+        self.used.set(0);
+    }
+
+    /// Bumps the arena, yielding a (well-aligned) pointer to `size`
+    /// unborrowed bytes.
+    fn alloc (self: &'_ StackArena<N>, size: usize, align: usize)
+      -> Option<*mut u8>
+    {
+        if size == 0 {
+            // Dangling but well-aligned.
+            return Some(align as *mut u8);
+        }
+        let base = self.buf.get() as *mut u8;
+        let start = (base as usize).checked_add(self.used.get())?;
+        let aligned = start.checked_add(align - 1)? & !(align - 1);
+        let offset = aligned - base as usize;
+        let end = offset.checked_add(size)?;
+        if end > N {
+            return None;
+        }
+        self.used.set(end);
+        Some(unsafe {
+            // Safety: `offset < end <= N`.
+            base.add(offset)
+        })
+    }
+
+    /// Moves `value` into the arena, if it fits (otherwise, `value` is
+    /// dropped).
+    #[inline]
+    pub
+    fn stackbox<'arena, T : 'arena> (
+        self: &'arena StackArena<N>,
+        value: T,
+    ) -> Option<StackBox<'arena, T>>
+    {
+        self.uninit_stackbox().map(|it| it.write(value))
+    }
+
+    /// Allocates room for a `T`, if it fits, to be initialized afterwards
+    /// with [`.write()`][`StackBox::write`].
+    #[inline]
+    pub
+    fn uninit_stackbox<'arena, T : 'arena> (self: &'arena StackArena<N>)
+      -> Option<StackBox<'arena, MaybeUninit<T>>>
+    {
+        let ptr = self.alloc(mem::size_of::<T>(), mem::align_of::<T>())?;
+        Some(unsafe {
+            // Safety: the bytes are exclusively borrowed for `'arena`, and
+            // `MaybeUninit` has no drop glue.
+            StackBox::from_raw(ptr.cast())
+        })
+    }
+
+    /// Allocates room for `len` `T`s, if they fit, to be initialized
+    /// afterwards with [`.init_from_fn()`][`StackBox::init_from_fn`].
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ::stackbox::prelude::*;
+    /// use ::stackbox::StackArena;
+    ///
+    /// let arena = StackArena::<64>::new();
+    /// let n = 5; // runtime length.
+    /// let squares: StackBox<'_, [u32]> =
+    ///     arena.uninit_slice(n).unwrap().init_from_fn(|i| (i * i) as u32)
+    /// ;
+    /// assert_eq!(*squares, [0, 1, 4, 9, 16]);
+    /// assert!(arena.uninit_slice::<u32>(42).is_none());
+    /// ```
+    #[inline]
+    pub
+    fn uninit_slice<'arena, T : 'arena> (
+        self: &'arena StackArena<N>,
+        len: usize,
+    ) -> Option<StackBox<'arena, [MaybeUninit<T>]>>
+    {
+        let size = mem::size_of::<T>().checked_mul(len)?;
+        let ptr = self.alloc(size, mem::align_of::<T>())?;
+        Some(unsafe {
+            // Safety: the bytes are exclusively borrowed for `'arena`, and
+            // `MaybeUninit` has no drop glue.
+            StackBox::from_raw(ptr::slice_from_raw_parts_mut(ptr.cast(), len))
+        })
+    }
+
+    /// Copies `slice` into the arena, if it fits.
+    #[inline]
+    pub
+    fn copy_slice<'arena, T : 'arena + Copy> (
+        self: &'arena StackArena<N>,
+        slice: &'_ [T],
+    ) -> Option<StackBox<'arena, [T]>>
+    {
+        let mut uninit = self.uninit_slice::<T>(slice.len())?;
+        Some(unsafe {
+            // Safety: all the items are initialized by the copy.
+            ptr::copy_nonoverlapping(
+                slice.as_ptr(),
+                uninit.as_mut_ptr().cast(),
+                slice.len(),
+            );
+            uninit.assume_init()
+        })
+    }
+
+    /// Copies `s` into the arena, if it fits.
+    #[inline]
+    pub
+    fn copy_str<'arena> (self: &'arena StackArena<N>, s: &'_ str)
+      -> Option<StackBox<'arena, str>>
+    {
+        let bytes = self.copy_slice(s.as_bytes())?;
+        Some(unsafe {
+            // Safety: copied from a `str`.
+            bytes.into_str_unchecked()
+        })
+    }
+}
+
+impl<const N: usize> Default for StackArena<N> {
+    #[inline]
+    fn default ()
+      -> StackArena<N>
+    {
+        StackArena::new()
+    }
+}
+
+impl<const N: usize>
+    ::core::fmt::Debug
+for
+    StackArena<N>
+{
+    fn fmt (self: &'_ Self, f: &'_ mut ::core::fmt::Formatter<'_>)
+      -> ::core::fmt::Result
+    {
+        f   .debug_struct("StackArena")
+            .field("capacity", &N)
+            .field("remaining", &self.remaining())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::stackbox::prelude::*;
+    use ::stackbox::StackArena;
+
+    #[test]
+    fn alignment ()
+    {
+        let arena = StackArena::<32>::new();
+        let byte = arena.stackbox(42_u8).unwrap();
+        assert_eq!(arena.remaining(), 31);
+        let word = arena.stackbox(27_u64).unwrap();
+        assert_eq!(&*word as *const u64 as usize % 8, 0);
+        assert!(arena.remaining() <= 32 - 9);
+        assert!(arena.stackbox([0_u64; 3]).is_none());
+        let unit = arena.stackbox(()).unwrap();
+        let empty = arena.copy_slice::<u64>(&[]).unwrap();
+        assert_eq!((*byte, *word, *unit, empty.len()), (42, 27, (), 0));
+        assert_eq!(
+            format!("{:?}", arena),
+            format!("StackArena {{ capacity: 32, remaining: {} }}", arena.remaining()),
+        );
+    }
+
+    #[test]
+    fn unsized_and_reset ()
+    {
+        let mut arena = StackArena::<8>::default();
+        let s: StackBox<'_, str> = arena.copy_str("Hello").unwrap();
+        assert_eq!(&*s, "Hello");
+        assert!(arena.copy_slice(&[0_u8; 4]).is_none());
+        let bytes: StackBox<'_, [u8]> = arena.copy_slice(&[1, 2, 3]).unwrap();
+        assert_eq!(*bytes, [1, 2, 3]);
+        assert_eq!(arena.remaining(), 0);
+        drop((s, bytes));
+        arena.reset();
+        assert_eq!(arena.remaining(), arena.capacity());
+    }
+
+    #[test]
+    fn test_drops ()
+    {
+        let rc = ::std::rc::Rc::new(());
+        let count = || ::std::rc::Rc::strong_count(&rc);
+        let rc = || rc.clone();
+
+        let arena = StackArena::<256>::new();
+        let a = arena.stackbox(rc()).unwrap();
+        let b = arena.stackbox([rc(), rc()]).unwrap();
+        assert_eq!(count(), 4);
+        drop(a);
+        assert_eq!(count(), 3);
+        drop(b);
+        assert_eq!(count(), 1);
+    }
+}
@@ -28,58 +28,21 @@ mod private {
     ///  - `dyn Any + Sync` → `StackBoxDynAny<dyn Sync>`;
     ///
     ///  - `dyn Any + Send + Sync` → `StackBoxDynAny<dyn Send + Sync>`;
+    ///
+    /// ### Conversions
+    ///
+    /// It can losslessly be converted from and into the matching
+    /// `StackBox<'frame, dyn Any + AutoTraits>` through [`From`] / [`Into`].
     pub
     struct StackBoxDynAny<
             'frame,
             AutoTraits : ?Sized + T::Sendness + T::Syncness = NoAutoTraits,
         >
     {
-        ptr: ptr::NonNull<ty::Erased>,
-        vtable: &'frame VTable,
+        stackbox: StackBox<'frame, dyn Any + 'static>,
         _auto_traits: ::core::marker::PhantomData<AutoTraits>,
     }
 
-    struct VTable {
-        drop_in_place: unsafe fn(ptr: ptr::NonNull<ty::Erased>),
-        /// could be a `const` if that constructor was made `const`
-        type_id: fn() -> TypeId,
-        as_Any:
-            unsafe
-            fn(ptr: ptr::NonNull<ty::Erased>) -> ptr::NonNull<dyn Any + 'static>
-        ,
-    }
-
-    impl<T> HasVTable for T
-    where
-        Self : Sized + Any,
-    {}
-    trait HasVTable
-    where
-        Self : Sized + Any,
-    {
-        const VTABLE: VTable = VTable {
-            drop_in_place: {
-                unsafe
-                fn drop_in_place<Self_> (ptr: ptr::NonNull<ty::Erased>)
-                {
-                    ptr::drop_in_place(ptr.cast::<Self_>().as_ptr())
-                }
-                drop_in_place::<Self>
-            },
-            type_id: || TypeId::of::<Self>(),
-            as_Any: {
-                unsafe
-                fn it<Self_ : Any + 'static> (ptr: ptr::NonNull<ty::Erased>)
-                  -> ptr::NonNull<dyn Any + 'static>
-                {
-                    let ptr: ptr::NonNull<Self_> = ptr.cast();
-                    ptr as ptr::NonNull<dyn Any + 'static>
-                }
-                it::<Self>
-            },
-        };
-    }
-
     define_coercions! {
         [Send] => dyn Send,
         [Sync] => dyn Sync,
@@ -104,9 +67,57 @@ mod private {
                 fn fatten (it: StackBox<'frame, T>)
                   -> Self
                 {
+                    let ptr: *mut (dyn Any + 'static) = it.into_raw();
                     StackBoxDynAny {
-                        vtable: &<T as HasVTable>::VTABLE,
-                        ptr: unsafe { ::core::mem::transmute(it) },
+                        stackbox: unsafe {
+                            // Safety: ownership is transferred.
+                            StackBox::from_raw(ptr)
+                        },
+                        _auto_traits: ::core::marker::PhantomData,
+                    }
+                }
+            }
+
+            impl<'frame>
+                From<StackBoxDynAny<'frame, $Marker>>
+            for
+                StackBox<'frame, dyn Any $(+ $AutoTrait)* + 'static>
+            {
+                #[inline]
+                fn from (it: StackBoxDynAny<'frame, $Marker>)
+                  -> Self
+                {
+                    let ptr: *mut (dyn Any + 'static) = it.stackbox.into_raw();
+                    unsafe {
+                        // Safety: ownership is transferred, and the
+                        // `$AutoTrait`s are guaranteed by the `$Marker`.
+                        StackBox::from_raw(::core::mem::transmute::<
+                            *mut (dyn Any + 'static),
+                            *mut (dyn Any $(+ $AutoTrait)* + 'static),
+                        >(ptr))
+                    }
+                }
+            }
+
+            impl<'frame, 'any : 'frame>
+                From<StackBox<'frame, dyn Any $(+ $AutoTrait)* + 'any>>
+            for
+                StackBoxDynAny<'frame, $Marker>
+            {
+                #[inline]
+                fn from (it: StackBox<'frame, dyn Any $(+ $AutoTrait)* + 'any>)
+                  -> Self
+                {
+                    let ptr: *mut (dyn Any + 'any) = it.into_raw();
+                    StackBoxDynAny {
+                        stackbox: unsafe {
+                            // Safety: ownership is transferred, and `Any` is
+                            // only implemented for `'static` types.
+                            StackBox::from_raw(::core::mem::transmute::<
+                                *mut (dyn Any + 'any),
+                                *mut (dyn Any + 'static),
+                            >(ptr))
+                        },
                         _auto_traits: ::core::marker::PhantomData,
                     }
                 }
@@ -131,7 +142,7 @@ mod private {
         fn type_id (self: &'_ Self)
           -> TypeId
         {
-            (self.vtable.type_id)()
+            Any::type_id(&*self.stackbox)
         }
 
         #[inline]
@@ -147,13 +158,7 @@ mod private {
         fn downcast_ref<U : Any> (self: &'_ Self)
           -> Option<&'_ U>
         {
-            if self.is::<U>() {
-                unsafe {
-                    Some(::core::mem::transmute(self.ptr))
-                }
-            } else {
-                None
-            }
+            self.stackbox.downcast_ref()
         }
 
         #[inline]
@@ -161,13 +166,7 @@ mod private {
         fn downcast_mut<U : Any> (self: &'_ mut Self)
           -> Option<&'_ mut U>
         {
-            if self.is::<U>() {
-                unsafe {
-                    Some(::core::mem::transmute(self.ptr))
-                }
-            } else {
-                None
-            }
+            self.stackbox.downcast_mut()
         }
 
         #[inline]
@@ -178,14 +177,10 @@ mod private {
                 Self,
             >
         {
-            if self.is::<U>() {
-                unsafe {
-                    let ptr = ::core::mem::ManuallyDrop::new(self).ptr;
-                    Ok(::core::mem::transmute(ptr))
-                }
-            } else {
-                Err(self)
-            }
+            let Self { stackbox, _auto_traits } = self;
+            stackbox
+                .downcast()
+                .map_err(|stackbox| Self { stackbox, _auto_traits })
         }
 
         #[inline]
@@ -213,8 +208,11 @@ mod private {
                           -> &'_ (dyn Any $(+ $($auto_traits)+)? + 'static)
                         {
                             unsafe {
-                                ::core::mem::transmute(
-                                    (self.vtable.as_Any)(self.ptr)
+                                ::core::mem::transmute::<
+                                    &'_ (dyn Any + 'static),
+                                    _,
+                                >(
+                                    &*self.stackbox
                                 )
                             }
                         }
@@ -250,8 +248,11 @@ mod private {
                           -> &'_ mut (dyn Any $(+ $($auto_traits)+)? + 'static)
                         {
                             unsafe {
-                                ::core::mem::transmute(
-                                    (self.vtable.as_Any)(self.ptr)
+                                ::core::mem::transmute::<
+                                    &'_ mut (dyn Any + 'static),
+                                    _,
+                                >(
+                                    &mut *self.stackbox
                                 )
                             }
                         }
@@ -263,19 +264,6 @@ mod private {
         }
     }
 
-    impl<'frame, AutoTraits : ?Sized + T::Sendness + T::Syncness>
-        Drop
-    for
-        StackBoxDynAny<'frame, AutoTraits>
-    {
-        fn drop (self: &'_ mut Self)
-        {
-            unsafe {
-                (self.vtable.drop_in_place)(self.ptr)
-            }
-        }
-    }
-
     impl<'frame, AutoTraits : ?Sized + T::Sendness + T::Syncness>
         ::core::fmt::Debug
     for
@@ -2,6 +2,8 @@ use super::*;
 
 use ::core::ops::Not as _;
 
+use crate::compile_fail;
+
 mod any {
     use super::*;
 
@@ -79,6 +81,23 @@ mod any {
         assert_eq!(count(), 1);
     }
 
+    #[test]
+    fn convert_from_and_into_dyn_any ()
+    {
+        use ::core::any::Any;
+
+        stackbox!(let stackbox = 42_i32);
+        let dyn_any: StackBoxDynAny<'_, dyn Send> = stackbox.into_dyn();
+        let stackbox: StackBox<'_, dyn Any + Send> = dyn_any.into();
+        let dyn_any: StackBoxDynAny<'_, dyn Send> = stackbox.into();
+        let stackbox: StackBox<'_, i32> = dyn_any.downcast().unwrap();
+        assert_eq!(*stackbox, 42);
+
+        stackbox!(let stackbox: StackBox<'_, dyn Any> = ());
+        let dyn_any = StackBoxDynAny::from(stackbox);
+        assert!(dyn_any.is::<()>());
+    }
+
     compile_fail! {
         #![name = cannot_coerce_unsync_into_sync_any]
 
@@ -324,33 +343,3 @@ mod custom_dyn {
         assert_eq!(it.type_id(), any::TypeId::of::<()>());
     }
 }
-
-macro_rules! compile_fail {(#[doc = $doc:expr] $item:item) => (#[doc = $doc] $item); (
-    #![name = $name:ident]
-    $($code:tt)*
-) => (
-    compile_fail! {
-        #[doc = concat!(
-            "```rust,",
-                "compile_fail", /* Comment to show the error messages */
-            "\n",
-            stringify! {
-                use ::stackbox::prelude::*;
-
-                fn main ()
-                {
-                    fn main ()
-                    {}
-
-                    {
-                        main();
-
-                        $($code)*
-                    }
-                }
-            }, "\n",
-            "```", "\n",
-        )]
-        pub mod $name {}
-    }
-)} use compile_fail;
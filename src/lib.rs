@@ -9,7 +9,7 @@
     no_std,
 )]
 #![cfg_attr(
-    feature = "const-generics",
+    all(feature = "const-generics", not(stackbox_const_generics)),
     feature(min_const_generics),
 )]
 
@@ -23,6 +23,11 @@ extern crate alloc;
 #[cfg(test)]
 extern crate self as stackbox;
 
+#[cfg(any(stackbox_const_generics, feature = "const-generics"))]
+pub use arena::StackArena;
+#[cfg(any(stackbox_const_generics, feature = "const-generics"))]
+mod arena;
+
 pub
 mod dyn_traits;
 
@@ -33,10 +38,18 @@ mod ptr;
 pub use slot::{mk_slot, Slot};
 mod slot;
 
-pub use stackbox_mod::StackBox;
+#[cfg(any(stackbox_const_generics, feature = "const-generics"))]
+pub use slots::Slots;
+#[cfg(any(stackbox_const_generics, feature = "const-generics"))]
+mod slots;
+
+pub use stackbox_mod::{iter, IntoFields, Overflow, StackBox, StackString, StackVec};
 #[path = "stackbox/mod.rs"]
 mod stackbox_mod;
 
+pub use tracked_slot::{TrackedBox, TrackedSlot};
+mod tracked_slot;
+
 /// This crates prelude: usage of this crate is designed to be ergonomic
 /// provided all the items within this module are in scope.
 pub
@@ -44,22 +57,29 @@ mod prelude {
     #[doc(no_inline)]
     pub use crate::{
         custom_dyn,
+        derive_into_fields,
+        emplace,
         dyn_traits::{
             any::StackBoxDynAny,
             fn_once::*,
         },
         mk_slot,
         mk_slots,
+        stack_format,
         stackbox,
         StackBox,
+        StackString,
+        StackVec,
     };
 }
 
 #[doc(hidden)] /** Macro internals, not subject to semver rules */ pub
 mod __ {
     pub use ::core::{
+        cell::Cell,
         marker::Sized,
-        mem::ManuallyDrop,
+        mem::{drop, ManuallyDrop},
+        pin::Pin,
     };
 
     pub trait GetVTable {
@@ -67,6 +87,7 @@ mod __ {
     }
     pub use ::core::{
         concat,
+        format_args,
         marker::{PhantomData, Send, Sync},
         mem::transmute,
         ops::Drop,
@@ -75,6 +96,7 @@ mod __ {
     pub use crate::{
         marker::{Sendness::T as Sendness, Syncness::T as Syncness, NoAutoTraits},
         dyn_traits::__::DynCoerce,
+        slot::EmplaceField,
     };
     mod ty { pub struct Erased(()); }
     pub type ErasedPtr = ::core::ptr::NonNull<ty::Erased>;
@@ -85,3 +107,36 @@ mod __ {
         ::core::ptr::drop_in_place::<T>(ptr.cast::<T>().as_ptr());
     }
 }
+
+/// Defines a `compile_fail` doctest out of the given statements, which are
+/// wrapped in a function body.
+#[cfg(any(test, doctest))]
+macro_rules! compile_fail {(#[doc = $doc:expr] $item:item) => (#[doc = $doc] $item); (
+    #![name = $name:ident]
+    $($code:tt)*
+) => (
+    compile_fail! {
+        #[doc = concat!(
+            "```rust,",
+                "compile_fail", /* Comment to show the error messages */
+            "\n",
+            stringify! {
+                use ::stackbox::prelude::*;
+
+                fn main ()
+                {
+                    fn main ()
+                    {}
+
+                    {
+                        main();
+
+                        $($code)*
+                    }
+                }
+            }, "\n",
+            "```", "\n",
+        )]
+        pub mod $name {}
+    }
+)} #[cfg(any(test, doctest))] pub(in crate) use compile_fail;
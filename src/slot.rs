@@ -1,8 +1,10 @@
 use crate::prelude::*;
 
 use ::core::{
+    cell::Cell,
     // marker::PhantomData,
     mem,
+    ops::Not as _,
 };
 
 /// Same as [`Slot::VACANT`], but using function call syntax to avoid firing
@@ -32,8 +34,12 @@ fn mk_slot<T> ()
 /// If needed, multiple such slots can be defined within the local scope and
 /// bound to a variadic number of identifiers (variable names) using the
 /// [`mk_slots!`][`crate::mk_slots`] macro.
+///
+/// A `Slot` does not know whether the value it was fed is still alive: see
+/// [`TrackedSlot`][`crate::TrackedSlot`] for a slot that does.
 pub
 struct Slot<T> {
+    pub(in crate)
     place: mem::MaybeUninit<T>,
     // /// Invariant lifetime just in case.
     // _borrow_mut_once: PhantomData<fn(&()) -> &mut &'frame ()>,
@@ -69,6 +75,128 @@ impl<T> Slot<T> {
         }
     }
 
+    /// Yields an owned [`StackBox`] of the (uninitialized) slot, which can
+    /// later be initialized with [`.write()`][`StackBox::write`] or
+    /// [`.assume_init()`][`StackBox::assume_init`].
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ::core::mem::MaybeUninit;
+    /// use ::stackbox::prelude::*;
+    ///
+    /// mk_slots!(slot);
+    /// let uninit: StackBox<'_, MaybeUninit<String>> = slot.uninit_stackbox();
+    /// let s: StackBox<'_, String> = uninit.write("Hello".into());
+    /// assert_eq!(*s, "Hello");
+    /// ```
+    #[inline]
+    pub
+    fn uninit_stackbox<'frame> (self: &'frame mut Slot<T>)
+      -> StackBox<'frame, mem::MaybeUninit<T>>
+    where
+        T : 'frame,
+    {
+        unsafe {
+            // Safety: `MaybeUninit` has no drop glue.
+            StackBox::from_raw(&mut self.place)
+        }
+    }
+
+    /// Initializes the slot in place, with the given callback, so as to
+    /// avoid building the value elsewhere and then moving it into the slot.
+    ///
+    /// See also the [`emplace!`][`crate::emplace`] macro, which offers a
+    /// non-`unsafe` way to initialize a `struct` field by field.
+    ///
+    /// # Safety
+    ///
+    /// The callback must have fully initialized the value when it returns.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ::stackbox::prelude::*;
+    ///
+    /// mk_slots!(slot);
+    /// let table: StackBox<'_, [u32; 4096]> = unsafe {
+    ///     slot.init_with(|uninit| {
+    ///         let ptr = uninit.as_mut_ptr() as *mut u32;
+    ///         for i in 0 .. 4096 {
+    ///             ptr.add(i).write(i as u32);
+    ///         }
+    ///     })
+    /// };
+    /// assert_eq!(table[4095], 4095);
+    /// ```
+    #[inline]
+    pub
+    unsafe
+    fn init_with<'frame> (
+        self: &'frame mut Slot<T>,
+        init: impl FnOnce(&'_ mut mem::MaybeUninit<T>),
+    ) -> StackBox<'frame, T>
+    where
+        T : 'frame,
+    {
+        init(&mut self.place);
+        StackBox::assume_owns(Self::assume_init_raw(self))
+    }
+
+    /// Fallible version of [`Slot::init_with`]: if the callback fails, the
+    /// slot is left vacant, and the error is returned.
+    ///
+    /// # Safety
+    ///
+    /// The callback must have fully initialized the value when it returns
+    /// `Ok`. When it returns `Err`, the partially initialized fields are
+    /// leaked (not dropped).
+    #[inline]
+    pub
+    unsafe
+    fn try_init_with<'frame, E> (
+        self: &'frame mut Slot<T>,
+        init: impl FnOnce(&'_ mut mem::MaybeUninit<T>) -> Result<(), E>,
+    ) -> Result<StackBox<'frame, T>, E>
+    where
+        T : 'frame,
+    {
+        init(&mut self.place)?;
+        Ok(StackBox::assume_owns(Self::assume_init_raw(self)))
+    }
+
+    /// # Safety
+    ///
+    /// The value must have been initialized.
+    #[inline]
+    unsafe
+    fn assume_init_raw<'frame> (this: &'frame mut Slot<T>)
+      -> &'frame mut ::core::mem::ManuallyDrop<T>
+    {
+        mem::transmute::<
+            &'_ mut mem::MaybeUninit<T>,
+            &'_ mut mem::ManuallyDrop<T>,
+        >(
+            &mut this.place,
+        )
+    }
+
+    #[doc(hidden)] /** Not part of the public API */ pub
+    fn __as_mut_ptr (this: &'_ mut Slot<T>)
+      -> *mut T
+    {
+        this.place.as_mut_ptr()
+    }
+
+    #[doc(hidden)] /** Not part of the public API */ pub
+    fn __check_fn<F : FnOnce(&'_ mut T)> (
+        _: &'_ &'_ mut Slot<T>,
+        check: F,
+    ) -> F
+    {
+        check
+    }
+
     #[doc(hidden)] /** Not part of the public API */ pub
     fn __init_raw<'frame> (this: &'frame mut Slot<T>, value: T)
       -> &'frame mut ::core::mem::ManuallyDrop<T>
@@ -76,12 +204,7 @@ impl<T> Slot<T> {
         this.place = mem::MaybeUninit::new(value);
         unsafe {
             // Safety: value has been initialized.
-            mem::transmute::<
-                &'_ mut mem::MaybeUninit<T>,
-                &'_ mut mem::ManuallyDrop<T>,
-            >(
-                &mut this.place,
-            )
+            Self::assume_init_raw(this)
         }
     }
 }
@@ -120,3 +243,254 @@ macro_rules! mk_slots {(
         let ref mut $var_name = $crate::prelude::mk_slot();
     )+
 )}
+
+/// Initializes a `struct` in place, field by field, within the given
+/// [`Slot`], to yield a [`StackBox`] of it.
+///
+/// This avoids building the whole `struct` elsewhere and then moving it into
+/// the slot: each field is directly written to its final address.
+///
+/// All the fields must be provided (exactly once), and the `struct` cannot be
+/// `#[repr(packed)]`. The field values are evaluated, and written, in order:
+/// should one of them panic or return early (_e.g._, through `?`), the fields
+/// written so far are dropped.
+///
+/// ## Example
+///
+/// ```rust
+/// use ::stackbox::prelude::*;
+///
+/// struct Decoder {
+///     name: &'static str,
+///     table: [u16; 1 << 16],
+/// }
+///
+/// mk_slots!(slot);
+/// let decoder: StackBox<'_, Decoder> = emplace!(slot, Decoder {
+///     name: "identity",
+///     table: [0; 1 << 16],
+/// });
+/// assert_eq!(decoder.name, "identity");
+/// ```
+#[macro_export]
+macro_rules! emplace {(
+    $slot:expr, $($Struct:ident)::+ {
+        $(
+            $field:ident : $value:expr
+        ),* $(,)?
+    } $(,)?
+) => (match $slot { slot => {
+    #[allow(unreachable_code)] {
+        if false {
+            // All the fields have been provided, exactly once.
+            let _ = $crate::Slot::stackbox(slot, $($Struct)::+ {
+                $(
+                    $field: loop {},
+                )*
+            });
+        }
+    }
+    // None of the fields is `#[repr(packed)]`-misaligned (a hard error on
+    // newer compilers, and a lint, here denied, on older ones).
+    #[allow(renamed_and_removed_lints, unknown_lints)]
+    #[deny(safe_packed_borrows)]
+    let _check = $crate::Slot::__check_fn(&slot, |it| {
+        $(
+            let _: &'_ mut _ = &mut it.$field;
+        )*
+    });
+    let ptr = $crate::Slot::__as_mut_ptr(slot);
+    // Until set, the written fields are dropped by their guards.
+    let done = $crate::__::Cell::new(false);
+    $(
+        let _guard = unsafe {
+            // Safety: in-bounds (and aligned) pointer to an (uninit) field.
+            $crate::__::EmplaceField::new(&mut (*ptr).$field, &done)
+        }.write($value);
+    )*
+    done.set(true);
+    unsafe {
+        // Safety: all the fields have been initialized.
+        $crate::Slot::init_with(slot, |_| ())
+    }
+}})}
+
+/// A pointer to a not yet initialized field of an `emplace!`d `struct`.
+#[doc(hidden)] /** Not part of the public API */ pub
+struct EmplaceField<'done, T> {
+    ptr: *mut T,
+    done: &'done Cell<bool>,
+}
+
+impl<'done, T> EmplaceField<'done, T> {
+    /// # Safety
+    ///
+    /// `ptr` must be valid for an (aligned) write, and must not be otherwise
+    /// used until `done` is set.
+    #[inline]
+    pub
+    unsafe
+    fn new (ptr: *mut T, done: &'done Cell<bool>)
+      -> EmplaceField<'done, T>
+    {
+        EmplaceField { ptr, done }
+    }
+
+    #[inline(always)]
+    pub
+    fn write (self: EmplaceField<'done, T>, value: T)
+      -> EmplaceGuard<'done, T>
+    {
+        unsafe {
+            // Safety: upheld by the caller of `new`.
+            self.ptr.write(value);
+        }
+        EmplaceGuard(self)
+    }
+}
+
+/// Drops the written field, unless `done` has been set.
+#[doc(hidden)] /** Not part of the public API */ pub
+struct EmplaceGuard<'done, T>(EmplaceField<'done, T>);
+
+impl<'done, T> Drop for EmplaceGuard<'done, T> {
+    fn drop (self: &'_ mut EmplaceGuard<'done, T>)
+    {
+        if self.0.done.get().not() {
+            unsafe {
+                // Safety: the field has been written, and is not owned by
+                // anything else.
+                ::core::ptr::drop_in_place(self.0.ptr);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    struct Big<T> {
+        rc: T,
+        table: [u8; 1 << 12],
+    }
+
+    #[test]
+    fn emplace ()
+    {
+        let rc = ::std::rc::Rc::new(());
+        let count = || ::std::rc::Rc::strong_count(&rc);
+
+        mk_slots!(slot);
+        let big = emplace!(slot, Big {
+            table: [42; 1 << 12],
+            rc: rc.clone(),
+        });
+        assert_eq!(big.table[0], 42);
+        assert_eq!(count(), 2);
+        drop(big);
+        assert_eq!(count(), 1);
+    }
+
+    #[test]
+    fn emplace_early_return ()
+    {
+        struct S { n: u8, s: String }
+
+        fn try_emplace (n: Option<u8>) -> Option<String>
+        {
+            mk_slots!(slot);
+            let it = emplace!(slot, S { s: String::from("x"), n: n? });
+            let S { n, s } = it.into_inner();
+            Some(format!("{}{}", s, n))
+        }
+
+        assert_eq!(try_emplace(None), None);
+        assert_eq!(try_emplace(Some(0)).unwrap(), "x0");
+    }
+
+    #[test]
+    fn emplace_unwind ()
+    {
+        use ::std::rc::Rc;
+
+        struct S<T> { a: T, b: T, c: T }
+
+        let rc = Rc::new(());
+        let count = || Rc::strong_count(&rc);
+
+        let caught = ::std::panic::catch_unwind(
+            ::std::panic::AssertUnwindSafe(|| {
+                mk_slots!(slot);
+                let _ = emplace!(slot, S {
+                    a: rc.clone(),
+                    b: rc.clone(),
+                    c: if count() == 3 {
+                        panic!("`a` and `b` have been written")
+                    } else {
+                        rc.clone()
+                    },
+                });
+            })
+        );
+        assert!(caught.is_err());
+        assert_eq!(count(), 1);
+
+        let early_return = || -> Option<()> {
+            mk_slots!(slot);
+            let _ = emplace!(slot, S { a: rc.clone(), b: rc.clone(), c: None? });
+            Some(())
+        };
+        assert!(early_return().is_none());
+        assert_eq!(count(), 1);
+    }
+
+    #[test]
+    fn try_init_with ()
+    {
+        mk_slots!(slot1, slot2);
+        let res = unsafe {
+            slot1.try_init_with(|_: &mut ::core::mem::MaybeUninit<u8>| Err(()))
+        };
+        assert!(res.is_err());
+        let it = unsafe {
+            slot2.try_init_with(|uninit| {
+                *uninit = ::core::mem::MaybeUninit::new(42);
+                Ok::<_, ()>(())
+            })
+        };
+        assert_eq!(*it.unwrap(), 42);
+    }
+}
+
+#[cfg(any(test, doctest))]
+mod compile_fail_tests {
+    use crate::compile_fail;
+
+    compile_fail! {
+        #![name = emplace_missing_field]
+        struct Struct { a: u8, b: u8 }
+        mk_slots!(slot);
+        let _ = emplace!(slot, Struct { a: 0 });
+    }
+
+    compile_fail! {
+        #![name = emplace_duplicate_field]
+        struct Struct { a: u8 }
+        mk_slots!(slot);
+        let _ = emplace!(slot, Struct { a: 0, a: 1 });
+    }
+
+    compile_fail! {
+        #![name = emplace_packed]
+        #[repr(C, packed)]
+        struct Struct { a: u8, b: u32 }
+        mk_slots!(slot);
+        let _ = emplace!(slot, Struct { a: 0, b: 0 });
+    }
+
+    compile_fail! {
+        #![name = emplace_unsafe_value]
+        struct Struct { a: u8 }
+        mk_slots!(slot);
+        let _ = emplace!(slot, Struct { a: *(&0_u8 as *const u8) });
+    }
+}
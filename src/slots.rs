@@ -0,0 +1,204 @@
+use crate::prelude::*;
+
+use ::core::{
+    cell::{Cell, UnsafeCell},
+    mem::MaybeUninit,
+};
+
+/// A pool of `N` [`Slot`]s of type `T`, handing out [`StackBox`]es through a
+/// shared reference.
+///
+/// Contrary to [`mk_slots!`], which declares a fixed set of named slots, a
+/// `Slots` pool can be fed values from within a loop or a recursive function,
+/// all of the so-obtained `StackBox`es sharing the lifetime of that borrow of
+/// the pool.
+///
+/// Individual slots are never given back to the pool, so that the
+/// `StackBox`es remain plain owning pointers; the whole pool can nonetheless
+/// be [`.reset()`][`Slots::reset`] once none of them is alive anymore.
+///
+/// [`Slot`]: `crate::Slot`
+/// [`mk_slots!`]: `crate::mk_slots`
+///
+/// ## Example: an owned linked list on the stack
+///
+/// ```rust
+/// use ::stackbox::prelude::*;
+/// use ::stackbox::Slots;
+///
+/// struct Node<'pool> {
+///     value: i32,
+///     next: Option<StackBox<'pool, Node<'pool>>>,
+/// }
+///
+/// let pool = Slots::<Node<'_>, 8>::new();
+/// let mut head = None;
+/// for value in 0 .. 5 {
+///     let node = Node { value, next: head.take() };
+///     head = Some(pool.stackbox(node).unwrap_or_else(|_| panic!()));
+/// }
+/// let mut values = vec![];
+/// let mut cursor = head.as_deref();
+/// while let Some(node) = cursor {
+///     values.push(node.value);
+///     cursor = node.next.as_deref();
+/// }
+/// assert_eq!(values, [4, 3, 2, 1, 0]);
+/// assert_eq!(pool.remaining(), 3);
+/// ```
+pub
+struct Slots<T, const N: usize> {
+    slots: UnsafeCell<MaybeUninit<[T; N]>>,
+    /// Invariant: `used <= N`, and the slots past `used` are not borrowed.
+    used: Cell<usize>,
+}
+
+impl<T, const N: usize> Slots<T, N> {
+    /// A pool of `N` vacant slots.
+    #[inline]
+    pub
+    const
+    fn new ()
+      -> Slots<T, N>
+    {
+        Slots {
+            slots: UnsafeCell::new(MaybeUninit::uninit()),
+            used: Cell::new(0),
+        }
+    }
+
+    /// The `N` in `Slots<T, N>`.
+    #[inline]
+    pub
+    fn capacity (self: &'_ Slots<T, N>)
+      -> usize
+    {
+        N
+    }
+
+    /// How many slots have been handed out.
+    #[inline]
+    pub
+    fn len (self: &'_ Slots<T, N>)
+      -> usize
+    {
+        self.used.get()
+    }
+
+    #[inline]
+    pub
+    fn is_empty (self: &'_ Slots<T, N>)
+      -> bool
+    {
+        self.len() == 0
+    }
+
+    /// How many slots can still be handed out.
+    #[inline]
+    pub
+    fn remaining (self: &'_ Slots<T, N>)
+      -> usize
+    {
+        N - self.used.get()
+    }
+
+    /// Moves `value` into the next vacant slot, or gives it back if the pool
+    /// is exhausted.
+    #[inline]
+    pub
+    fn stackbox<'pool> (self: &'pool Slots<T, N>, value: T)
+      -> Result<StackBox<'pool, T>, T>
+    where
+        T : 'pool,
+    {
+        let used = self.used.get();
+        if used == N {
+            return Err(value);
+        }
+        self.used.set(used + 1);
+        Ok(unsafe {
+            // Safety: the slot at `used` was vacant, and is now exclusively
+            // borrowed for `'pool`.
+            let ptr = (self.slots.get() as *mut T).add(used);
+            ptr.write(value);
+            StackBox::from_raw(ptr)
+        })
+    }
+
+    /// Makes all the slots vacant again.
+    ///
+    /// The exclusive borrow guarantees that no `StackBox` obtained from the
+    /// pool is still alive.
+    #[inline]
+    pub
+    fn reset (self: &'_ mut Slots<T, N>)
+    {
+        self.used.set(0);
+    }
+}
+
+impl<T, const N: usize> Default for Slots<T, N> {
+    #[inline]
+    fn default ()
+      -> Slots<T, N>
+    {
+        Slots::new()
+    }
+}
+
+impl<T, const N: usize>
+    ::core::fmt::Debug
+for
+    Slots<T, N>
+{
+    fn fmt (self: &'_ Self, f: &'_ mut ::core::fmt::Formatter<'_>)
+      -> ::core::fmt::Result
+    {
+        f   .debug_struct("Slots")
+            .field("capacity", &N)
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::stackbox::prelude::*;
+    use ::stackbox::Slots;
+
+    #[test]
+    fn worklist ()
+    {
+        let mut pool = Slots::<String, 3>::default();
+        let mut worklist: Vec<StackBox<'_, String>> = vec![];
+        for s in "a b c d".split(' ') {
+            match pool.stackbox(s.into()) {
+                Ok(boxed) => worklist.push(boxed),
+                Err(s) => assert_eq!(s, "d"),
+            }
+        }
+        assert_eq!(format!("{:?}", pool), "Slots { capacity: 3, len: 3 }");
+        assert_eq!(worklist.pop().unwrap().into_inner(), "c");
+        assert_eq!((&worklist[0][..], &worklist[1][..]), ("a", "b"));
+        drop(worklist);
+        pool.reset();
+        assert!(pool.is_empty());
+        assert_eq!(*pool.stackbox("e".into()).unwrap(), "e");
+    }
+
+    #[test]
+    fn test_drops ()
+    {
+        let rc = ::std::rc::Rc::new(());
+        let count = || ::std::rc::Rc::strong_count(&rc);
+        let rc = || rc.clone();
+
+        let pool = Slots::<_, 2>::new();
+        let a = pool.stackbox(rc()).unwrap();
+        let b = pool.stackbox(rc()).unwrap();
+        drop(pool.stackbox(rc()).unwrap_err());
+        assert_eq!(count(), 3);
+        drop((a, b));
+        assert_eq!(count(), 1);
+    }
+}
@@ -0,0 +1,232 @@
+//! Collecting an iterator into a caller-provided, runtime-sized, buffer.
+
+use super::*;
+
+use ::core::{
+    fmt,
+    iter::{self, Chain, Once},
+    mem::MaybeUninit,
+};
+
+use super::{
+    slice::IsArray,
+    uninit::InitGuard,
+};
+
+impl<'frame, T : 'frame> StackBox<'frame, [T]> {
+    /// Collects the items of `iterable` into `buf`, yielding an owned slice
+    /// of exactly the collected length.
+    ///
+    /// Should `buf` be too small, an [`Overflow`] error gives back both the
+    /// filled prefix and the remaining items (including the one which did not
+    /// fit).
+    ///
+    /// Should the iterator panic, the already collected items are dropped.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ::core::mem::MaybeUninit;
+    /// use ::stackbox::prelude::*;
+    ///
+    /// fn evens (n: usize)
+    /// {
+    ///     // Poor man's `alloca`.
+    ///     let mut buf: [MaybeUninit<String>; 64] = unsafe {
+    ///         MaybeUninit::uninit().assume_init()
+    ///     };
+    ///     let buf = &mut buf[.. n];
+    ///     let iter = (0 ..).filter(|x| x % 2 == 0).map(|x| x.to_string());
+    ///     match StackBox::collect_into(buf, iter) {
+    ///         Ok(_) => unreachable!(),
+    ///         Err(overflow) => {
+    ///             assert_eq!(overflow.collected.len(), n);
+    ///             let mut remaining = overflow.remaining;
+    ///             assert_eq!(remaining.next().unwrap(), (2 * n).to_string());
+    ///         },
+    ///     };
+    /// }
+    ///
+    /// evens(3);
+    /// evens(42);
+    /// ```
+    pub
+    fn collect_into<Iterable : IntoIterator<Item = T>> (
+        buf: &'frame mut [MaybeUninit<T>],
+        iterable: Iterable,
+    ) -> Result<
+            StackBox<'frame, [T]>,
+            Overflow<'frame, T, Iterable::IntoIter>,
+        >
+    {
+        let capacity = buf.len();
+        let mut guard = InitGuard {
+            base: buf.as_mut_ptr().cast::<T>(),
+            initialized: 0,
+        };
+        let mut iter = iterable.into_iter();
+        while let Some(item) = iter.next() {
+            if guard.initialized == capacity {
+                return Err(Overflow {
+                    collected: unsafe {
+                        // Safety: `buf` is borrowed for `'frame`.
+                        guard.into_stackbox()
+                    },
+                    remaining: iter::once(item).chain(iter),
+                });
+            }
+            unsafe {
+                // Safety: `initialized < capacity`.
+                guard.push(item);
+            }
+        }
+        Ok(unsafe {
+            // Safety: `buf` is borrowed for `'frame`.
+            guard.into_stackbox()
+        })
+    }
+
+    /// Same as [`StackBox::collect_into`], but using a [`Slot`] of an array
+    /// `[T; N]` as the backing buffer.
+    ///
+    /// The same [requirements regarding `N`][`StackBox::into_slice`] apply.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ::stackbox::prelude::*;
+    ///
+    /// let words = "the quick brown fox jumps over the lazy dog".split(' ');
+    /// let slot = &mut mk_slot::<[_; 8]>();
+    /// let long_words: StackBox<'_, [&str]> =
+    ///     StackBox::collect_in(slot, words.filter(|w| w.len() > 4))
+    ///         .unwrap()
+    /// ;
+    /// assert_eq!(*long_words, ["quick", "brown", "jumps"]);
+    /// ```
+    #[inline]
+    pub
+    fn collect_in<Array, Iterable> (
+        slot: &'frame mut Slot<Array>,
+        iterable: Iterable,
+    ) -> Result<
+            StackBox<'frame, [T]>,
+            Overflow<'frame, T, Iterable::IntoIter>,
+        >
+    where
+        Array : IsArray<'frame, Item = T>,
+        Iterable : IntoIterator<Item = T>,
+    {
+        let buf = unsafe {
+            // Safety: `MaybeUninit`s have no drop glue, so forgetting about
+            // the owning `StackBox` is fine.
+            &mut *slot.uninit_stackbox().into_uninit_slice().into_raw()
+        };
+        StackBox::collect_into(buf, iterable)
+    }
+}
+
+/// The error returned by [`StackBox::collect_into`] when the iterator yields
+/// more items than the buffer can hold.
+pub
+struct Overflow<'frame, T, I> {
+    /// The items which fit in the buffer (all of it).
+    pub
+    collected: StackBox<'frame, [T]>,
+
+    /// The item which did not fit, followed by the rest of the iterator.
+    pub
+    remaining: Chain<Once<T>, I>,
+}
+
+impl<'frame, T, I>
+    fmt::Debug
+for
+    Overflow<'frame, T, I>
+where
+    T : fmt::Debug,
+{
+    fn fmt (self: &'_ Self, f: &'_ mut fmt::Formatter<'_>)
+      -> fmt::Result
+    {
+        f   .debug_struct("Overflow")
+            .field("collected", &self.collected)
+            .field("remaining", &format_args!(".."))
+            .finish()
+    }
+}
+
+impl<'frame, T, I>
+    fmt::Display
+for
+    Overflow<'frame, T, I>
+{
+    fn fmt (self: &'_ Self, f: &'_ mut fmt::Formatter<'_>)
+      -> fmt::Result
+    {
+        write!(f,
+            "the iterator yielded more items than the buffer capacity ({})",
+            self.collected.len(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::core::mem::MaybeUninit;
+    use ::stackbox::prelude::*;
+
+    #[test]
+    fn collect ()
+    {
+        let mut buf = [MaybeUninit::<String>::uninit(), MaybeUninit::uninit()];
+        let empty = StackBox::collect_into(&mut buf[.. 0], None).unwrap();
+        assert!(empty.is_empty());
+        drop(empty);
+
+        let strings = vec!["a".to_string(), "b".to_string()];
+        let collected = StackBox::collect_into(&mut buf, strings).unwrap();
+        assert_eq!(*collected, ["a", "b"]);
+
+        mk_slots!(slot);
+        let overflow = StackBox::collect_in::<[_; 2], _>(slot, 1 ..= 5).unwrap_err();
+        assert_eq!(
+            overflow.to_string(),
+            "the iterator yielded more items than the buffer capacity (2)",
+        );
+        assert_eq!(
+            format!("{:?}", overflow),
+            "Overflow { collected: [1, 2], remaining: .. }",
+        );
+        assert_eq!(*overflow.collected, [1, 2]);
+        assert!(overflow.remaining.eq(3 ..= 5));
+    }
+
+    #[test]
+    fn test_drops ()
+    {
+        let rc = ::std::rc::Rc::new(());
+        let count = || ::std::rc::Rc::strong_count(&rc);
+        let rc = || rc.clone();
+
+        mk_slots!(slot);
+        let overflow = StackBox::collect_in::<[_; 2], _>(
+            slot,
+            vec![rc(), rc(), rc(), rc()],
+        ).unwrap_err();
+        assert_eq!(count(), 5);
+        drop(overflow);
+        assert_eq!(count(), 1);
+
+        let caught = ::std::panic::catch_unwind(
+            ::std::panic::AssertUnwindSafe(|| {
+                StackBox::collect_in::<[_; 4], _>(
+                    &mut mk_slot(),
+                    (0 .. 4).map(|i| { assert!(i < 2); rc() }),
+                ).map(drop).ok();
+            })
+        );
+        assert!(caught.is_err());
+        assert_eq!(count(), 1);
+    }
+}
@@ -0,0 +1,152 @@
+//! Owned downcasting of the `StackBox<'_, dyn Any…>` and
+//! `StackBox<'_, dyn Error…>` trait objects, mirroring that of `Box`.
+
+#![allow(nonstandard_style)]
+
+use super::*;
+
+use ::core::any::Any;
+
+impl_downcast_for_dyn_Any! {
+    (),
+    (+ Send),
+    (+ Sync),
+    (+ Send + Sync),
+}
+
+macro_rules! impl_downcast_for_dyn_Any {(
+    $(
+        ( $(+ $AutoTrait:ident)* )
+    ),* $(,)?
+) => (
+    $(
+        impl<'frame, 'any : 'frame>
+            StackBox<'frame, dyn Any $(+ $AutoTrait)* + 'any>
+        {
+            /// Attempts to downcast the box to a concrete type.
+            ///
+            /// ## Example
+            ///
+            /// ```rust
+            /// use ::core::any::Any;
+            /// use ::stackbox::prelude::*;
+            ///
+            /// stackbox!(let it: StackBox<'_, dyn Any> = 42_i32);
+            /// let it = it.downcast::<u8>().unwrap_err();
+            /// let it: StackBox<'_, i32> = it.downcast().unwrap();
+            /// assert_eq!(*it, 42);
+            /// ```
+            #[inline]
+            pub
+            fn downcast<T : Any> (self: Self)
+              -> Result<StackBox<'frame, T>, Self>
+            {
+                let it: &'_ (dyn Any + 'any) = &*self;
+                let it: &'_ (dyn Any + 'static) = unsafe {
+                    // Safety: `Any` is only implemented for `'static` types.
+                    ::core::mem::transmute(it)
+                };
+                if it.is::<T>() {
+                    Ok(unsafe {
+                        // Safety: the pointee is a `T`.
+                        StackBox::from_raw(self.into_raw() as *mut T)
+                    })
+                } else {
+                    Err(self)
+                }
+            }
+        }
+    )*
+)} use impl_downcast_for_dyn_Any;
+
+#[cfg(feature = "std")]
+mod error {
+    use super::*;
+
+    use ::std::error::Error;
+
+    impl_downcast_for_dyn_Error! {
+        (),
+        (+ Send),
+        (+ Sync),
+        (+ Send + Sync),
+    }
+
+    macro_rules! impl_downcast_for_dyn_Error {(
+        $(
+            ( $(+ $AutoTrait:ident)* )
+        ),* $(,)?
+    ) => (
+        $(
+            impl<'frame>
+                StackBox<'frame, dyn Error $(+ $AutoTrait)* + 'static>
+            {
+                /// Attempts to downcast the box to a concrete type.
+                ///
+                /// Note that, contrary to `Box<dyn Error>`, the `'static`
+                /// bound of the trait object needs to be spelled out:
+                /// `StackBox<'_, dyn Error + 'static>`.
+                #[inline]
+                pub
+                fn downcast<T : Error + 'static> (self: Self)
+                  -> Result<StackBox<'frame, T>, Self>
+                {
+                    let it: &'_ (dyn Error + 'static) = &*self;
+                    if it.is::<T>() {
+                        Ok(unsafe {
+                            // Safety: the pointee is a `T`.
+                            StackBox::from_raw(self.into_raw() as *mut T)
+                        })
+                    } else {
+                        Err(self)
+                    }
+                }
+            }
+        )*
+    )} use impl_downcast_for_dyn_Error;
+}
+
+#[cfg(test)]
+mod tests {
+    use ::core::any::Any;
+    use ::stackbox::prelude::*;
+
+    #[test]
+    fn test_drops ()
+    {
+        let rc = ::std::rc::Rc::new(());
+        let count = || ::std::rc::Rc::strong_count(&rc);
+        let rc = || rc.clone();
+
+        stackbox!(let it: StackBox<'_, dyn Any> = rc());
+        assert_eq!(count(), 2);
+        let it = it.downcast::<()>().unwrap_err();
+        assert_eq!(count(), 2);
+        let it = it.downcast::<::std::rc::Rc<()>>().unwrap();
+        assert_eq!(count(), 2);
+        drop(it);
+        assert_eq!(count(), 1);
+    }
+
+    #[test]
+    fn auto_traits ()
+    {
+        stackbox!(let it: StackBox<'_, dyn Any + Send + Sync> = 42_u8);
+        let it: StackBox<'_, u8> = it.downcast().unwrap();
+        assert_eq!(*it, 42);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn error ()
+    {
+        use ::std::{error::Error, fmt, io};
+
+        stackbox!(let it: StackBox<'_, dyn Error + Send + Sync + 'static> =
+            io::Error::new(io::ErrorKind::Other, "io")
+        );
+        let it = it.downcast::<fmt::Error>().unwrap_err();
+        let it: StackBox<'_, io::Error> = it.downcast().unwrap();
+        assert_eq!(it.kind(), io::ErrorKind::Other);
+    }
+}
@@ -0,0 +1,282 @@
+//! Owned `CStr`, `OsStr` and `Path` slices.
+//!
+//! `std` does not guarantee the layout of these types, so the in-place
+//! conversions below assume that each of them is a (transparent) wrapper
+//! around a byte slice, and check that assumption (see `cast_in_place!`).
+
+use super::*;
+
+use super::map_in_place::SameLayout;
+
+use ::std::{
+    ffi::{CStr, FromBytesWithNulError, OsStr},
+    path::Path,
+};
+
+impl<'frame> StackBox<'frame, [u8]> {
+    /// Converts an owned, NUL-terminated, byte slice into an owned [`CStr`],
+    /// in place, after checking that it has no interior NUL bytes.
+    ///
+    /// On failure, the bytes are given back, alongside the error.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ::std::ffi::CStr;
+    /// use ::stackbox::prelude::*;
+    ///
+    /// stackbox!(let bytes: StackBox<'_, [u8]> = *b"Hello\0");
+    /// let c_str: StackBox<'_, CStr> = bytes.into_c_str().unwrap();
+    /// assert_eq!(c_str.to_str(), Ok("Hello"));
+    /// ```
+    #[inline]
+    pub
+    fn into_c_str (self: StackBox<'frame, [u8]>)
+      -> Result<
+            StackBox<'frame, CStr>,
+            (FromBytesWithNulError, StackBox<'frame, [u8]>),
+        >
+    {
+        if let Err(err) = CStr::from_bytes_with_nul(&self) {
+            return Err((err, self));
+        }
+        Ok(unsafe {
+            // Safety: the bytes have just been checked. `CStr` is assumed to
+            // wrap a `[u8]` which includes the trailing NUL: should it ever
+            // become a thin pointer, or drop the NUL from its length, the
+            // cast is rejected.
+            cast_in_place!(self: [u8] => CStr, |bytes| {
+                CStr::from_bytes_with_nul_unchecked(bytes)
+            })
+        })
+    }
+
+    /// Converts an owned byte slice into an owned [`OsStr`], in place.
+    #[cfg(unix)]
+    #[inline]
+    pub
+    fn into_os_str (self: StackBox<'frame, [u8]>)
+      -> StackBox<'frame, OsStr>
+    {
+        use ::std::os::unix::ffi::OsStrExt;
+        unsafe {
+            // Safety: on Unix, any byte slice is a valid `OsStr`, which is
+            // assumed to wrap the very same `[u8]` that `from_bytes` views.
+            cast_in_place!(self: [u8] => OsStr, |bytes| OsStr::from_bytes(bytes))
+        }
+    }
+}
+
+impl<'frame> StackBox<'frame, CStr> {
+    /// Converts an owned [`CStr`] back into its owned bytes, in place,
+    /// including the trailing NUL byte.
+    #[inline]
+    pub
+    fn into_bytes_with_nul (self: StackBox<'frame, CStr>)
+      -> StackBox<'frame, [u8]>
+    {
+        unsafe {
+            // Safety: the reverse of `into_c_str`, under the same assumption
+            // about `CStr`'s layout.
+            cast_in_place!(self: CStr => [u8], |c_str| c_str.to_bytes_with_nul())
+        }
+    }
+}
+
+impl<'frame> StackBox<'frame, str> {
+    /// Converts an owned string slice into an owned [`OsStr`], in place.
+    #[inline]
+    pub
+    fn into_os_str (self: StackBox<'frame, str>)
+      -> StackBox<'frame, OsStr>
+    {
+        unsafe {
+            // Safety: UTF-8 is a valid `OsStr` encoding on every platform
+            // (including Windows' WTF-8), and `OsStr` is assumed to wrap the
+            // very same bytes that `OsStr::new` views.
+            cast_in_place!(self: str => OsStr, |s| OsStr::new(s))
+        }
+    }
+
+    /// Converts an owned string slice into an owned [`Path`], in place.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ::std::path::Path;
+    /// use ::stackbox::prelude::*;
+    ///
+    /// stack_format!(let path = 64; "{}/{}", "dir", "file.txt");
+    /// let path: StackBox<'_, Path> = path.unwrap().into_path();
+    /// assert_eq!(path.extension().unwrap(), "txt");
+    /// ```
+    #[inline]
+    pub
+    fn into_path (self: StackBox<'frame, str>)
+      -> StackBox<'frame, Path>
+    {
+        self.into_os_str().into_path()
+    }
+}
+
+impl<'frame> StackBox<'frame, OsStr> {
+    /// Converts an owned [`OsStr`] into an owned [`Path`], in place.
+    #[inline]
+    pub
+    fn into_path (self: StackBox<'frame, OsStr>)
+      -> StackBox<'frame, Path>
+    {
+        unsafe {
+            // Safety: any `OsStr` is a valid `Path`, which is assumed to wrap
+            // it as `Path::new` does.
+            cast_in_place!(self: OsStr => Path, |os_str| Path::new(os_str))
+        }
+    }
+
+    /// Converts an owned [`OsStr`] into an owned string slice, in place, if
+    /// it is valid Unicode. Otherwise, it is given back.
+    #[inline]
+    pub
+    fn into_str (self: StackBox<'frame, OsStr>)
+      -> Result<StackBox<'frame, str>, StackBox<'frame, OsStr>>
+    {
+        if self.to_str().is_none() {
+            return Err(self);
+        }
+        Ok(unsafe {
+            // Safety: valid Unicode is stored as UTF-8, in place, as the
+            // borrowing `to_str` conversion shows (which the cast checks).
+            cast_in_place!(self: OsStr => str, |os_str| os_str.to_str().unwrap())
+        })
+    }
+
+    /// Converts an owned [`OsStr`] back into its owned bytes, in place.
+    #[cfg(unix)]
+    #[inline]
+    pub
+    fn into_bytes (self: StackBox<'frame, OsStr>)
+      -> StackBox<'frame, [u8]>
+    {
+        use ::std::os::unix::ffi::OsStrExt;
+        unsafe {
+            // Safety: the reverse of `StackBox::<[u8]>::into_os_str`, under
+            // the same assumption about `OsStr`'s layout.
+            cast_in_place!(self: OsStr => [u8], |os_str| os_str.as_bytes())
+        }
+    }
+}
+
+impl<'frame> StackBox<'frame, Path> {
+    /// Converts an owned [`Path`] into an owned [`OsStr`], in place.
+    #[inline]
+    pub
+    fn into_os_str (self: StackBox<'frame, Path>)
+      -> StackBox<'frame, OsStr>
+    {
+        unsafe {
+            // Safety: the reverse of `StackBox::<OsStr>::into_path`, under the
+            // same assumption about `Path`'s layout.
+            cast_in_place!(self: Path => OsStr, |path| path.as_os_str())
+        }
+    }
+}
+
+/// Casts an owned `$Src` into an owned `$Dst`, in place, given the documented
+/// borrowing conversion, `$view`, between the two.
+///
+/// Since the layouts involved are not guaranteed by `std`, the cast is
+/// checked, when the code is built (not under `cargo check`), to involve
+/// same-sized (fat) pointers, and, at runtime, to yield exactly the pointer
+/// (address and metadata) `$view` does. On mismatch, it panics (leaking the
+/// pointee).
+///
+/// # Safety
+///
+/// The pointee must be a valid `$Dst`, and `$Dst` must wrap its data the way
+/// `$view` shows.
+macro_rules! cast_in_place {(
+    $it:ident : $Src:ty => $Dst:ty, |$src:ident| $view:expr $(,)?
+) => ({
+    let () = SameLayout::<*mut $Src, *mut $Dst>::CHECK;
+    let ptr: *mut $Src = $it.into_raw();
+    let expected: *const $Dst = {
+        let $src: &'_ $Src = &*ptr;
+        $view
+    };
+    let casted = ptr as *mut $Dst;
+    assert!(::core::ptr::eq(casted, expected),
+        "unsupported `{}` layout", stringify!($Dst),
+    );
+    StackBox::from_raw(casted)
+})} use cast_in_place;
+
+forward_as_ref! {
+    str => OsStr, Path;
+    OsStr => Path;
+    Path => OsStr;
+} macro_rules! forward_as_ref {(
+    $(
+        $T:ty => $($Target:ty),+;
+    )*
+) => (
+    $($(
+        impl<'frame>
+            AsRef<$Target>
+        for
+            StackBox<'frame, $T>
+        {
+            #[inline]
+            fn as_ref (self: &'_ Self)
+              -> &'_ $Target
+            {
+                (**self).as_ref()
+            }
+        }
+    )+)*
+)} use forward_as_ref;
+
+#[cfg(test)]
+mod tests {
+    use ::std::{ffi::{CStr, OsStr}, path::Path};
+    use ::stackbox::prelude::*;
+
+    #[test]
+    fn c_str ()
+    {
+        stackbox!(let bytes: StackBox<'_, [u8]> = *b"a\0b\0");
+        let (_, bytes) = bytes.into_c_str().unwrap_err();
+        let (a, _) = bytes.stackbox_split_at(2);
+        let a: StackBox<'_, CStr> = a.into_c_str().unwrap();
+        assert_eq!(a.to_bytes(), b"a");
+        assert_eq!(*a.into_bytes_with_nul(), *b"a\0");
+    }
+
+    #[test]
+    fn os_str_and_path ()
+    {
+        fn takes_path (path: impl AsRef<Path>) -> bool
+        {
+            path.as_ref().ends_with("c")
+        }
+
+        stack_format!(let s = 8; "a/b/c");
+        let s = s.unwrap();
+        assert!(takes_path(&s));
+        let path = s.into_path();
+        assert!(takes_path(&path));
+        assert_eq!(AsRef::<OsStr>::as_ref(&path), "a/b/c");
+        let os_str = path.into_os_str();
+        assert!(takes_path(&os_str));
+        assert_eq!(&*os_str.into_str().unwrap(), "a/b/c");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn os_str_bytes ()
+    {
+        stackbox!(let bytes: StackBox<'_, [u8]> = [b'a', 0xff]);
+        let os_str = bytes.into_os_str();
+        let os_str = os_str.into_str().unwrap_err();
+        assert_eq!(*os_str.into_bytes(), [b'a', 0xff]);
+    }
+}
@@ -0,0 +1,268 @@
+//! Owned field projections: `StackBox<(A, B)> → (StackBox<A>, StackBox<B>)`.
+
+use super::*;
+
+/// Types whose owned fields can each be given their own [`StackBox`],
+/// in place.
+///
+/// It is implemented for tuples (up to 12 elements), and can be implemented
+/// for custom `struct`s with the [`derive_into_fields!`] macro: a
+/// `macro_rules!` macro (not a `#[derive]`), to which every field has to be
+/// listed alongside its type.
+///
+/// [`derive_into_fields!`]: `crate::derive_into_fields`
+pub
+trait IntoFields<'frame> : 'frame + Sized {
+    /// A tuple of [`StackBox`]es, one per field.
+    type Fields : 'frame;
+
+    fn into_fields (this: StackBox<'frame, Self>)
+      -> Self::Fields
+    ;
+}
+
+impl<'frame, T : IntoFields<'frame>> StackBox<'frame, T> {
+    /// Splits a `StackBox` of a tuple (or of a `struct` for which
+    /// [`IntoFields`] has been implemented) into one `StackBox` per field,
+    /// without moving any of them.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ::stackbox::prelude::*;
+    ///
+    /// stackbox!(let pair = (String::from("Hello"), [0_u8; 1024]));
+    /// let (greeting, buffer): (StackBox<'_, String>, StackBox<'_, [u8; 1024]>) =
+    ///     pair.into_fields()
+    /// ;
+    /// assert_eq!(*greeting, "Hello");
+    /// drop(buffer);
+    /// ```
+    #[inline]
+    pub
+    fn into_fields (self: StackBox<'frame, T>)
+      -> T::Fields
+    {
+        T::into_fields(self)
+    }
+}
+
+/// Implements [`IntoFields`] for a custom `struct`, thus providing
+/// [`.into_fields()`][`StackBox::into_fields`] for `StackBox`es of it.
+///
+/// All the fields must be listed, with their types, in any order: the
+/// returned tuple of `StackBox`es follows that order.
+///
+/// The `struct` cannot implement `Drop` (as with moving out of its fields),
+/// nor be `#[repr(packed)]`: both are checked at compile time.
+///
+/// ## Example
+///
+/// ```rust
+/// use ::stackbox::prelude::*;
+///
+/// struct Big<T> {
+///     header: String,
+///     payload: [T; 4096],
+/// }
+///
+/// derive_into_fields! {
+///     for[T] Big<T> { header: String, payload: [T; 4096] }
+/// }
+///
+/// struct Pair(u8, String);
+///
+/// derive_into_fields! {
+///     Pair { 0: u8, 1: String }
+/// }
+///
+/// stackbox!(let big = Big { header: "header".into(), payload: [0_u8; 4096] });
+/// let (header, payload) = big.into_fields();
+/// assert_eq!(*header, "header");
+/// assert_eq!(payload.len(), 4096);
+/// ```
+#[macro_export]
+macro_rules! derive_into_fields {
+(
+    for [$($generics:tt)*]
+    $Ty:ty {
+        $(
+            $field:tt : $FieldTy:ty
+        ),+ $(,)?
+    }
+) => (
+    impl<'__frame, $($generics)*>
+        $crate::IntoFields<'__frame>
+    for
+        $Ty
+    where
+        Self : '__frame,
+    {
+        type Fields = (
+            $(
+                $crate::StackBox<'__frame, $FieldTy>,
+            )+
+        );
+
+        #[inline]
+        fn into_fields (this: $crate::StackBox<'__frame, Self>)
+          -> Self::Fields
+        {
+            // `Self` is not `#[repr(packed)]` (a hard error on newer
+            // compilers, and a lint, here denied, on older ones),
+            #[allow(renamed_and_removed_lints, unknown_lints)]
+            #[deny(safe_packed_borrows)]
+            let _check = |it: &'_ mut Self| {
+                $(
+                    let _: &'_ mut $FieldTy = &mut it.$field;
+                )+
+            };
+            let _check = |it: Self| {
+                // all the fields have been listed,
+                let Self { $($field: _),+ } = &it;
+                // and they can be moved out of `Self` (no `Drop` impl).
+                $(
+                    let _moved: $FieldTy = it.$field;
+                )+
+            };
+            let mut this = $crate::__::ManuallyDrop::new(this);
+            let ptr: *mut Self = &mut **this;
+            unsafe {
+                // Safety: ownership of each (disjoint) field is transferred.
+                ($(
+                    $crate::StackBox::assume_owns({
+                        let field: &'__frame mut $FieldTy = &mut (*ptr).$field;
+                        &mut *(field as *mut $FieldTy as *mut $crate::__::ManuallyDrop<$FieldTy>)
+                    }),
+                )+)
+            }
+        }
+    }
+);
+
+(
+    $Ty:ty {
+        $($fields:tt)*
+    }
+) => (
+    $crate::derive_into_fields! {
+        for [] $Ty { $($fields)* }
+    }
+)}
+
+macro_rules! impl_for_tuples {(
+    $(
+        ( $($T:ident . $i:tt),+ )
+    )*
+) => (
+    $(
+        impl<'frame, $($T : 'frame),+>
+            IntoFields<'frame>
+        for
+            ($($T ,)+)
+        {
+            type Fields = ($(StackBox<'frame, $T>,)+);
+
+            #[inline]
+            fn into_fields (this: StackBox<'frame, Self>)
+              -> Self::Fields
+            {
+                let ptr: *mut Self = this.into_raw();
+                unsafe {
+                    // Safety: ownership of each (disjoint) field is
+                    // transferred.
+                    ($(
+                        StackBox::from_raw(&mut (*ptr).$i as *mut $T),
+                    )+)
+                }
+            }
+        }
+    )*
+)}
+
+impl_for_tuples! {
+    (A.0)
+    (A.0, B.1)
+    (A.0, B.1, C.2)
+    (A.0, B.1, C.2, D.3)
+    (A.0, B.1, C.2, D.3, E.4)
+    (A.0, B.1, C.2, D.3, E.4, F.5)
+    (A.0, B.1, C.2, D.3, E.4, F.5, G.6)
+    (A.0, B.1, C.2, D.3, E.4, F.5, G.6, H.7)
+    (A.0, B.1, C.2, D.3, E.4, F.5, G.6, H.7, I.8)
+    (A.0, B.1, C.2, D.3, E.4, F.5, G.6, H.7, I.8, J.9)
+    (A.0, B.1, C.2, D.3, E.4, F.5, G.6, H.7, I.8, J.9, K.10)
+    (A.0, B.1, C.2, D.3, E.4, F.5, G.6, H.7, I.8, J.9, K.10, L.11)
+}
+
+#[cfg(test)]
+mod tests {
+    use ::stackbox::prelude::*;
+
+    #[test]
+    fn test_drops ()
+    {
+        let rc = ::std::rc::Rc::new(());
+        let count = || ::std::rc::Rc::strong_count(&rc);
+        let rc = || rc.clone();
+
+        stackbox!(let it = (rc(), 42, rc()));
+        assert_eq!(count(), 3);
+        let (a, b, c) = it.into_fields();
+        assert_eq!(*b, 42);
+        assert_eq!(count(), 3);
+        drop(c);
+        assert_eq!(count(), 2);
+        drop(a);
+        assert_eq!(count(), 1);
+    }
+
+    struct Struct<'a, T> {
+        a: &'a str,
+        t: T,
+    }
+
+    derive_into_fields! {
+        for['a, T] Struct<'a, T> { t: T, a: &'a str }
+    }
+
+    #[test]
+    fn structs ()
+    {
+        stackbox!(let it = Struct { a: "a", t: String::from("t") });
+        let (t, a) = it.into_fields();
+        assert_eq!(*a, "a");
+        assert_eq!(*t, "t");
+    }
+}
+
+#[cfg(any(test, doctest))]
+mod compile_fail_tests {
+    use crate::compile_fail;
+
+    compile_fail! {
+        #![name = missing_field]
+        struct Struct { a: u8, b: u8 }
+        derive_into_fields! { Struct { a: u8 } }
+    }
+
+    compile_fail! {
+        #![name = drop_impl]
+        struct Struct { a: String }
+        impl Drop for Struct { fn drop (&mut self) {} }
+        derive_into_fields! { Struct { a: String } }
+    }
+
+    compile_fail! {
+        #![name = packed]
+        #[repr(C, packed)]
+        struct Struct { a: u8, b: u32 }
+        derive_into_fields! { Struct { a: u8, b: u32 } }
+    }
+
+    compile_fail! {
+        #![name = wrong_field_type]
+        struct Struct { a: String }
+        derive_into_fields! { Struct { a: u8 } }
+    }
+}
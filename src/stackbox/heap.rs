@@ -0,0 +1,203 @@
+//! Conversions between [`StackBox`]es and the heap-allocated smart pointers.
+
+use super::*;
+
+use ::alloc::{
+    boxed::Box,
+    rc::Rc,
+    string::String,
+    sync::Arc,
+    vec::Vec,
+};
+
+impl<'frame, T : 'frame> StackBox<'frame, T> {
+    /// Moves the pointee onto the heap, so that it can escape `'frame`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ::stackbox::prelude::*;
+    ///
+    /// fn escape (it: StackBox<'_, String>) -> Box<String>
+    /// {
+    ///     it.into_box()
+    /// }
+    ///
+    /// stackbox!(let it = String::from("Hello, World!"));
+    /// let boxed = escape(it);
+    /// assert_eq!(*boxed, "Hello, World!");
+    /// ```
+    #[inline]
+    pub
+    fn into_box (self: StackBox<'frame, T>)
+      -> Box<T>
+    {
+        Box::new(StackBox::into_inner(self))
+    }
+
+    /// Moves the pointee into a new [`Rc`].
+    #[inline]
+    pub
+    fn into_rc (self: StackBox<'frame, T>)
+      -> Rc<T>
+    {
+        Rc::new(StackBox::into_inner(self))
+    }
+
+    /// Moves the pointee into a new [`Arc`].
+    #[inline]
+    pub
+    fn into_arc (self: StackBox<'frame, T>)
+      -> Arc<T>
+    {
+        Arc::new(StackBox::into_inner(self))
+    }
+
+    /// Moves the pointee of a [`Box`] into the given [`Slot`], thus
+    /// freeing the heap allocation.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ::stackbox::prelude::*;
+    ///
+    /// let slot = &mut mk_slot();
+    /// let it = StackBox::from_box_in(slot, Box::new(42));
+    /// assert_eq!(*it, 42);
+    /// ```
+    #[inline]
+    pub
+    fn from_box_in (slot: &'frame mut Slot<T>, boxed: Box<T>)
+      -> StackBox<'frame, T>
+    {
+        slot.stackbox(*boxed)
+    }
+}
+
+impl<'frame, Item : 'frame> StackBox<'frame, [Item]> {
+    /// Moves the items into a new [`Vec`], so that they can escape `'frame`.
+    pub
+    fn into_vec (self: StackBox<'frame, [Item]>)
+      -> Vec<Item>
+    {
+        let len = self.len();
+        let mut vec = Vec::with_capacity(len);
+        let this = ManuallyDrop::new(self);
+        unsafe {
+            // Safety: the items are moved out of `this`, which won't drop them.
+            ptr::copy_nonoverlapping(this.as_ptr(), vec.as_mut_ptr(), len);
+            vec.set_len(len);
+        }
+        vec
+    }
+
+    /// Moves the items into a new `Box<[Item]>`.
+    #[inline]
+    pub
+    fn into_box (self: StackBox<'frame, [Item]>)
+      -> Box<[Item]>
+    {
+        self.into_vec().into_boxed_slice()
+    }
+
+    /// Moves the items into a new `Rc<[Item]>`.
+    #[inline]
+    pub
+    fn into_rc (self: StackBox<'frame, [Item]>)
+      -> Rc<[Item]>
+    {
+        self.into_vec().into()
+    }
+
+    /// Moves the items into a new `Arc<[Item]>`.
+    #[inline]
+    pub
+    fn into_arc (self: StackBox<'frame, [Item]>)
+      -> Arc<[Item]>
+    {
+        self.into_vec().into()
+    }
+}
+
+impl<'frame> StackBox<'frame, str> {
+    /// Copies the string slice into a new [`String`], so that it can escape
+    /// `'frame`.
+    #[inline]
+    pub
+    fn into_string (self: StackBox<'frame, str>)
+      -> String
+    {
+        String::from(&*self)
+    }
+
+    /// Copies the string slice into a new `Box<str>`.
+    #[inline]
+    pub
+    fn into_box (self: StackBox<'frame, str>)
+      -> Box<str>
+    {
+        Box::from(&*self)
+    }
+
+    /// Copies the string slice into a new `Rc<str>`.
+    #[inline]
+    pub
+    fn into_rc (self: StackBox<'frame, str>)
+      -> Rc<str>
+    {
+        Rc::from(&*self)
+    }
+
+    /// Copies the string slice into a new `Arc<str>`.
+    #[inline]
+    pub
+    fn into_arc (self: StackBox<'frame, str>)
+      -> Arc<str>
+    {
+        Arc::from(&*self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::stackbox::prelude::*;
+
+    #[test]
+    fn test_drops ()
+    {
+        let rc = ::std::rc::Rc::new(());
+        let count = || ::std::rc::Rc::strong_count(&rc);
+        let rc = || rc.clone();
+
+        stackbox!(let it = rc());
+        let boxed = it.into_box();
+        assert_eq!(count(), 2);
+        drop(boxed);
+        assert_eq!(count(), 1);
+
+        stackbox!(let it: StackBox<'_, [_]> = [rc(), rc(), rc()]);
+        let (_, it) = it.stackbox_split_at(1);
+        assert_eq!(count(), 3);
+        let vec = it.into_vec();
+        assert_eq!(vec.len(), 2);
+        assert_eq!(count(), 3);
+        drop(vec);
+        assert_eq!(count(), 1);
+
+        let slot = &mut mk_slot();
+        let it = StackBox::from_box_in(slot, Box::new(rc()));
+        assert_eq!(count(), 2);
+        let arc = it.into_arc();
+        assert_eq!(count(), 2);
+        drop(arc);
+        assert_eq!(count(), 1);
+    }
+
+    #[test]
+    fn slices ()
+    {
+        stackbox!(let it: StackBox<'_, [_]> = [String::from("a"), "b".into()]);
+        let rc: ::std::rc::Rc<[String]> = it.into_rc();
+        assert_eq!(*rc, ["a", "b"]);
+    }
+}
@@ -0,0 +1,177 @@
+//! In-place mapping of the pointee(s), reusing the backing storage.
+
+use super::*;
+
+/// Compile-time check that `T` and `U` have the same layout.
+///
+/// It is only evaluated when the code using it is monomorphized and built:
+/// `cargo check` does not catch a mismatch.
+pub(in super)
+struct SameLayout<T, U> (T, U);
+
+impl<T, U> SameLayout<T, U> {
+    pub(in super)
+    const CHECK: () = [()][
+        (
+            ::core::mem::size_of::<T>() != ::core::mem::size_of::<U>()
+            ||
+            ::core::mem::align_of::<T>() != ::core::mem::align_of::<U>()
+        ) as usize // `T` and `U` must have the same size and alignment
+    ];
+}
+
+impl<'frame, T : 'frame> StackBox<'frame, T> {
+    /// Maps the pointee to a value of the same size and alignment, reusing
+    /// the backing storage.
+    ///
+    /// Layout compatibility is checked when the code is built (a mismatch is
+    /// a compilation error, albeit not reported by `cargo check`).
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ::stackbox::prelude::*;
+    ///
+    /// stackbox!(let it = String::from("Hi"));
+    /// let it: StackBox<'_, Vec<u8>> = it.map_in_place(String::into_bytes);
+    /// assert_eq!(*it, b"Hi");
+    /// ```
+    #[inline]
+    pub
+    fn map_in_place<U : 'frame> (
+        self: StackBox<'frame, T>,
+        f: impl FnOnce(T) -> U,
+    ) -> StackBox<'frame, U>
+    {
+        let () = SameLayout::<T, U>::CHECK;
+        let ptr: *mut T = self.into_raw();
+        unsafe {
+            // Safety: same layout, and ownership of the value is first moved
+            // out (should `f` panic, the storage is just left vacant).
+            let ptr = ptr as *mut U;
+            ptr.write(f(ptr.cast::<T>().read()));
+            StackBox::from_raw(ptr)
+        }
+    }
+}
+
+impl<'frame, T : 'frame> StackBox<'frame, [T]> {
+    /// Maps each item to a value of the same size and alignment, reusing
+    /// the backing storage, much like `Vec`'s in-place `collect`.
+    ///
+    /// Layout compatibility is checked when the code is built (a mismatch is
+    /// a compilation error, albeit not reported by `cargo check`).
+    ///
+    /// Should `f` panic, the already mapped items, as well as the yet unmapped
+    /// ones, are dropped.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ::stackbox::prelude::*;
+    ///
+    /// stackbox!(let bits: StackBox<'_, [_]> = [0_u32, 0x3f80_0000]);
+    /// let floats: StackBox<'_, [f32]> = bits.map_in_place(f32::from_bits);
+    /// assert_eq!(*floats, [0.0, 1.0]);
+    /// ```
+    pub
+    fn map_in_place<U : 'frame> (
+        self: StackBox<'frame, [T]>,
+        mut f: impl FnMut(T) -> U,
+    ) -> StackBox<'frame, [U]>
+    {
+        let () = SameLayout::<T, U>::CHECK;
+        let len = self.len();
+        let base: *mut T = self.into_raw() as _;
+        let mut guard = UnwindGuard::<T, U> {
+            base,
+            len,
+            cur: 0,
+            _marker: Default::default(),
+        };
+        while guard.cur < len {
+            unsafe {
+                // Safety: same layout, and on unwind, the item at `cur` is
+                // considered as moved out.
+                let at = base.add(guard.cur);
+                (at as *mut U).write(f(at.read()));
+            }
+            guard.cur += 1;
+        }
+        ::core::mem::forget(guard);
+        unsafe {
+            // Safety: all the items have been mapped.
+            StackBox::from_raw(
+                ::core::ptr::slice_from_raw_parts_mut(base as *mut U, len)
+            )
+        }
+    }
+}
+
+/// The items before `cur` are `U`s, the ones after it are `T`s, and the one
+/// at `cur` has been moved out.
+struct UnwindGuard<T, U> {
+    base: *mut T,
+    len: usize,
+    cur: usize,
+    _marker: ::core::marker::PhantomData<(T, U)>,
+}
+
+impl<T, U> Drop for UnwindGuard<T, U> {
+    fn drop (self: &'_ mut Self)
+    {
+        unsafe {
+            ::core::ptr::drop_in_place::<[U]>(
+                ::core::ptr::slice_from_raw_parts_mut(
+                    self.base as *mut U,
+                    self.cur,
+                )
+            );
+            ::core::ptr::drop_in_place::<[T]>(
+                ::core::ptr::slice_from_raw_parts_mut(
+                    self.base.add(self.cur + 1),
+                    self.len - self.cur - 1,
+                )
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::stackbox::prelude::*;
+
+    #[test]
+    fn test_drops ()
+    {
+        use ::std::rc::Rc;
+
+        let rc = Rc::new(());
+        let count = || Rc::strong_count(&rc);
+        let rc = || rc.clone();
+
+        stackbox!(let it = rc());
+        let it = it.map_in_place(|it| (it, ()));
+        assert_eq!(count(), 2);
+        drop(it);
+        assert_eq!(count(), 1);
+
+        stackbox!(let slice: StackBox<'_, [_]> = [rc(), rc(), rc()]);
+        let slice = slice.map_in_place(Some);
+        assert_eq!(count(), 4);
+        drop(slice);
+        assert_eq!(count(), 1);
+
+        stackbox!(let slice: StackBox<'_, [_]> = [(0, rc()), (1, rc()), (2, rc())]);
+        let caught = ::std::panic::catch_unwind(
+            ::std::panic::AssertUnwindSafe(|| {
+                slice.map_in_place(|(i, rc)| {
+                    assert!(i < 1);
+                    (rc, i)
+                })
+            })
+        );
+        assert!(caught.is_err());
+        assert_eq!(count(), 1);
+    }
+}
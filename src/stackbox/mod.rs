@@ -4,9 +4,36 @@ use crate::{prelude::*,
     Slot,
 };
 
-pub use slice::iter;
+pub use slice::{iter, StackVec};
 mod slice;
 
+pub use collect::Overflow;
+mod collect;
+
+mod downcast;
+
+#[cfg(feature = "std")]
+mod ffi;
+
+pub use fields::IntoFields;
+mod fields;
+
+#[cfg(feature = "alloc")]
+mod heap;
+
+mod map_in_place;
+
+mod pin;
+
+mod std_traits;
+
+pub use string::StackString;
+mod string;
+
+mod transpose;
+
+mod uninit;
+
 /// Stack<sup>1</sup>-allocated `Box`. Think of this as of `&'frame mut T`, but
 /// with `move` semantics (no reborrowing!) which allow the "reference" to drop
 /// its pointee.
@@ -305,6 +332,34 @@ impl<'frame, T : ?Sized + 'frame> StackBox<'frame, T> {
             ::core::ptr::read(&this.unique_ptr)
         }
     }
+
+    /// Raw pointer constructor.
+    ///
+    /// # Safety
+    ///
+    /// Same requirements as [`StackBox::assume_owns`].
+    #[inline]
+    pub(in crate)
+    unsafe
+    fn from_raw (ptr: *mut T)
+      -> StackBox<'frame, T>
+    {
+        StackBox {
+            unique_ptr: ptr::Unique::from_raw(ptr),
+            _covariant_lt: Default::default(),
+        }
+    }
+
+    /// Yields ownership of the pointee, which won't be dropped.
+    #[inline]
+    pub(in crate)
+    fn into_raw (self: StackBox<'frame, T>)
+      -> *mut T
+    {
+        self.into_inner_unique()
+            .into_raw_nonnull()
+            .as_ptr()
+    }
 }
 
 impl<'frame, T : ?Sized + 'frame>
@@ -461,6 +516,25 @@ macro_rules! stackbox {
         $crate::stackbox!(let $var = $var)
     );
 
+    (
+        // Create a new pinned `StackBox` without mentioning the backing _slot_.
+        // The owning `StackBox` is kept in a hygienic (unnameable) binding, so
+        // that it cannot be leaked, and `$var` is a `Pin<&mut …>` to it.
+        // Examples:
+        //   - `stackbox!(pin let fut = async { … });`
+        //   - `stackbox!(pin let fut: StackBox<dyn Future<…>> = async { … });`
+        pin let $var:ident $(: $T:ty)? = $expr:expr
+    ) => (
+        let ref mut ptr = $crate::__::ManuallyDrop::new($expr);
+        let mut boxed $(: $T)? = unsafe { $crate::StackBox::assume_owns(ptr) };
+        #[allow(unused_mut)]
+        let mut $var = unsafe {
+            // Safety: `boxed` cannot be named, let alone moved or leaked, so
+            // its pointee is dropped in place before the frame ends.
+            $crate::__::Pin::new_unchecked(&mut *boxed)
+        };
+    );
+
     (
         // To be used as a temporary fed to a function parameter, or as a
         // `[::with_locals::with]` "return" value.
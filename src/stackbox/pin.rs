@@ -0,0 +1,169 @@
+//! `Pin<StackBox<'_, T>>` support.
+//!
+//! A `StackBox` never moves its pointee, but, contrary to a `Box`, leaking it
+//! (_e.g._, through [`mem::forget`][`::core::mem::forget`]) does not prevent
+//! the backing memory from being reclaimed once `'frame` ends. Since `Pin`
+//! requires that the pointee be dropped before its memory gets invalidated,
+//! obtaining an owned `Pin<StackBox<'_, T>>` for a `T : !Unpin` is thus
+//! `unsafe`. The [`stackbox!`] macro's `pin let` form offers a non-`unsafe`
+//! alternative, by keeping the owning `StackBox` out of reach and only
+//! exposing a `Pin<&mut T>` to its pointee.
+//!
+//! Outside of `unsafe` code, an owned `Pin<StackBox<'_, T>>` can only be
+//! obtained through the `From` conversion, which requires `T : Unpin`. Such a
+//! pinned box can then be unsized (_e.g._, with the `unsize` feature) to a
+//! `!Unpin` trait object, but the pointee's concrete type remains `Unpin`, so
+//! leaking it is harmless.
+
+use super::*;
+
+use ::core::pin::Pin;
+
+impl<'frame, T : ?Sized + 'frame> StackBox<'frame, T> {
+    /// Converts a `StackBox<T>` into a `Pin<StackBox<T>>`, in place.
+    ///
+    /// See [`stackbox!`]'s `pin let` form for a non-`unsafe` alternative.
+    ///
+    /// # Safety
+    ///
+    /// The returned `Pin<StackBox<'frame, T>>` must not be leaked: its pointee
+    /// needs to be dropped before `'frame` ends, as mandated by [`Pin`]'s
+    /// `Drop` guarantee.
+    ///
+    /// This requirement is trivially met when `T : Unpin`, in which case the
+    /// non-`unsafe` `From` conversion can be used instead.
+    #[inline]
+    pub
+    unsafe
+    fn into_pin (self: StackBox<'frame, T>)
+      -> Pin<StackBox<'frame, T>>
+    {
+        Pin::new_unchecked(self)
+    }
+}
+
+impl<'frame, T : 'frame> StackBox<'frame, T> {
+    /// Pinned equivalent of [`StackBox::new_in`].
+    ///
+    /// # Safety
+    ///
+    /// Same requirements as [`StackBox::into_pin`].
+    #[inline]
+    pub
+    unsafe
+    fn pin_in (slot: &'frame mut Slot<T>, value: T)
+      -> Pin<StackBox<'frame, T>>
+    {
+        StackBox::new_in(slot, value).into_pin()
+    }
+}
+
+impl<'frame, T : ?Sized + Unpin + 'frame>
+    From<StackBox<'frame, T>>
+for
+    Pin<StackBox<'frame, T>>
+{
+    #[inline]
+    fn from (it: StackBox<'frame, T>)
+      -> Pin<StackBox<'frame, T>>
+    {
+        Pin::new(it)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::core::{
+        future::Future,
+        pin::Pin,
+        task::{Context, Poll, RawWaker, RawWakerVTable, Waker},
+    };
+    use ::stackbox::prelude::*;
+
+    fn noop_waker ()
+      -> Waker
+    {
+        const VTABLE: RawWakerVTable = RawWakerVTable::new(
+            |_| RawWaker::new(::core::ptr::null(), &VTABLE),
+            |_| (),
+            |_| (),
+            |_| (),
+        );
+        unsafe {
+            Waker::from_raw(RawWaker::new(::core::ptr::null(), &VTABLE))
+        }
+    }
+
+    #[test]
+    fn poll_pinned_future ()
+    {
+        let waker = noop_waker();
+        let cx = &mut Context::from_waker(&waker);
+
+        let ready = async { 42 };
+        stackbox!(pin let fut = async {
+            let it = ready.await;
+            let it_ref = &it; // `!Unpin` future
+            async {}.await;
+            *it_ref
+        });
+        let mut fut: Pin<&mut _> = fut;
+        assert_eq!(fut.as_mut().poll(cx), Poll::Ready(42));
+
+        stackbox!(pin let fut: StackBox<'_, dyn Future<Output = ()>> = async {});
+        assert_eq!(fut.as_mut().poll(cx), Poll::Ready(()));
+    }
+
+    #[test]
+    fn unpin_future ()
+    {
+        let waker = noop_waker();
+        let cx = &mut Context::from_waker(&waker);
+
+        struct Ready(i32);
+        impl Future for Ready {
+            type Output = i32;
+
+            fn poll (self: Pin<&'_ mut Self>, _: &'_ mut Context<'_>)
+              -> Poll<i32>
+            {
+                Poll::Ready(self.0)
+            }
+        }
+
+        mk_slots!(s1, s2);
+        let fut: StackBox<'_, dyn Future<Output = i32> + Unpin> = if true {
+            stackbox!(s1, Ready(42))
+        } else {
+            stackbox!(s2, Box::pin(async { 0 }))
+        };
+        stackbox!(pin let fut = async { fut.await + 27 });
+        assert_eq!(fut.as_mut().poll(cx), Poll::Ready(69));
+    }
+
+    #[test]
+    fn test_drops ()
+    {
+        let rc = ::std::rc::Rc::new(());
+        let count = || ::std::rc::Rc::strong_count(&rc);
+        let rc = || rc.clone();
+
+        mk_slots!(slot);
+        let pinned = unsafe { StackBox::pin_in(slot, rc()) };
+        assert_eq!(count(), 2);
+        drop(pinned);
+        assert_eq!(count(), 1);
+
+        stackbox!(let boxed = rc());
+        let pinned: Pin<StackBox<'_, _>> = boxed.into();
+        assert_eq!(count(), 2);
+        drop(pinned);
+        assert_eq!(count(), 1);
+
+        {
+            stackbox!(pin let _pinned = rc());
+            assert_eq!(count(), 2);
+        }
+        assert_eq!(count(), 1);
+    }
+}
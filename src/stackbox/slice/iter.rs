@@ -1,9 +1,72 @@
+//! Owned iterators over [`StackBox`]`<'_, [Item]>` slices.
+
+use ::core::mem::ManuallyDrop;
 use crate::prelude::*;
 
+/// Owned iterator over the items of a [`StackBox`]`<'_, [Item]>`, akin to
+/// [`vec::IntoIter`](https://doc.rust-lang.org/std/vec/struct.IntoIter.html).
 pub
-struct Iter<'frame, Item : 'frame> (
-    StackBox<'frame, [Item]>,
-);
+struct Iter<'frame, Item : 'frame> {
+    /// Invariant: the items in `start .. end` are owned.
+    base: ::core::ptr::NonNull<Item>,
+    start: usize,
+    end: usize,
+    _owns: ::core::marker::PhantomData<StackBox<'frame, [Item]>>,
+}
+
+unsafe // Safety: same as `StackBox<'frame, [Item]>`.
+    impl<'frame, Item : 'frame + Send> Send for Iter<'frame, Item>
+    {}
+
+unsafe // Safety: same as `StackBox<'frame, [Item]>`.
+    impl<'frame, Item : 'frame + Sync> Sync for Iter<'frame, Item>
+    {}
+
+impl<'frame, Item : 'frame> Iter<'frame, Item> {
+    /// The remaining items, as a slice.
+    #[inline]
+    pub
+    fn as_slice (self: &'_ Iter<'frame, Item>)
+      -> &'_ [Item]
+    {
+        unsafe {
+            // Safety: from the type invariant.
+            ::core::slice::from_raw_parts(
+                self.base.as_ptr().add(self.start),
+                self.end - self.start,
+            )
+        }
+    }
+
+    /// The remaining items, as a mutable slice.
+    #[inline]
+    pub
+    fn as_mut_slice (self: &'_ mut Iter<'frame, Item>)
+      -> &'_ mut [Item]
+    {
+        unsafe {
+            // Safety: from the type invariant.
+            ::core::slice::from_raw_parts_mut(
+                self.base.as_ptr().add(self.start),
+                self.end - self.start,
+            )
+        }
+    }
+
+    /// Recovers ownership of the remaining items, as an owned slice.
+    #[inline]
+    pub
+    fn into_stackbox (self: Iter<'frame, Item>)
+      -> StackBox<'frame, [Item]>
+    {
+        let mut this = ManuallyDrop::new(self);
+        let remaining: *mut [Item] = this.as_mut_slice();
+        unsafe {
+            // Safety: ownership of the remaining items is transferred.
+            StackBox::from_raw(remaining)
+        }
+    }
+}
 
 impl<'frame, Item : 'frame> Iterator for Iter<'frame, Item> {
     type Item = Item;
@@ -12,7 +75,88 @@ impl<'frame, Item : 'frame> Iterator for Iter<'frame, Item> {
     fn next (self: &'_ mut Iter<'frame, Item>)
       -> Option<Item>
     {
-        self.0.stackbox_pop()
+        if self.start == self.end {
+            return None;
+        }
+        self.start += 1;
+        Some(unsafe {
+            // Safety: the item is no longer in `start .. end`.
+            self.base.as_ptr().add(self.start - 1).read()
+        })
+    }
+
+    #[inline]
+    fn size_hint (self: &'_ Iter<'frame, Item>)
+      -> (usize, Option<usize>)
+    {
+        let len = self.end - self.start;
+        (len, Some(len))
+    }
+
+    #[inline]
+    fn count (self: Iter<'frame, Item>)
+      -> usize
+    {
+        self.len()
+    }
+}
+
+impl<'frame, Item : 'frame>
+    DoubleEndedIterator
+for
+    Iter<'frame, Item>
+{
+    #[inline]
+    fn next_back (self: &'_ mut Iter<'frame, Item>)
+      -> Option<Item>
+    {
+        if self.start == self.end {
+            return None;
+        }
+        self.end -= 1;
+        Some(unsafe {
+            // Safety: the item is no longer in `start .. end`.
+            self.base.as_ptr().add(self.end).read()
+        })
+    }
+}
+
+impl<'frame, Item : 'frame>
+    ExactSizeIterator
+for
+    Iter<'frame, Item>
+{}
+
+impl<'frame, Item : 'frame>
+    ::core::iter::FusedIterator
+for
+    Iter<'frame, Item>
+{}
+
+impl<'frame, Item : 'frame>
+    ::core::fmt::Debug
+for
+    Iter<'frame, Item>
+where
+    Item : ::core::fmt::Debug,
+{
+    fn fmt (self: &'_ Self, f: &'_ mut ::core::fmt::Formatter<'_>)
+      -> ::core::fmt::Result
+    {
+        f   .debug_tuple("Iter")
+            .field(&self.as_slice())
+            .finish()
+    }
+}
+
+impl<'frame, Item : 'frame> Drop for Iter<'frame, Item> {
+    #[inline]
+    fn drop (self: &'_ mut Iter<'frame, Item>)
+    {
+        unsafe {
+            // Safety: from the type invariant.
+            ::core::ptr::drop_in_place::<[Item]>(self.as_mut_slice())
+        }
     }
 }
 
@@ -26,10 +170,189 @@ impl<'frame, Item : 'frame> IntoIterator
     fn into_iter (self: StackBox<'frame, [Item]>)
       -> Iter<'frame, Item>
     {
-        Iter(self)
+        let end = self.len();
+        let base: *mut Item = self.into_raw() as _;
+        Iter {
+            base: unsafe {
+                // Safety: from a `StackBox`.
+                ::core::ptr::NonNull::new_unchecked(base)
+            },
+            start: 0,
+            end,
+            _owns: ::core::marker::PhantomData,
+        }
+    }
+}
+
+/// Yields the owned slice, leaving an empty one in its stead.
+fn take<'frame, Item : 'frame> (it: &'_ mut StackBox<'frame, [Item]>)
+  -> StackBox<'frame, [Item]>
+{
+    ::core::mem::replace(it, unsafe {
+        // Safety: empty slice.
+        StackBox::assume_owns_all(&mut [])
+    })
+}
+
+/// Owned iterator over `chunk_size`-long subslices.
+///
+/// See [`StackBox::stackbox_chunks`].
+pub
+struct Chunks<'frame, Item : 'frame> {
+    remaining: StackBox<'frame, [Item]>,
+    chunk_size: usize,
+}
+
+impl<'frame, Item : 'frame> Chunks<'frame, Item> {
+    pub(in super)
+    fn new (slice: StackBox<'frame, [Item]>, chunk_size: usize)
+      -> Self
+    {
+        assert!(chunk_size != 0, "chunk size must be non-zero");
+        Chunks { remaining: slice, chunk_size }
+    }
+}
+
+impl<'frame, Item : 'frame> Iterator for Chunks<'frame, Item> {
+    type Item = StackBox<'frame, [Item]>;
+
+    #[inline]
+    fn next (self: &'_ mut Chunks<'frame, Item>)
+      -> Option<StackBox<'frame, [Item]>>
+    {
+        let len = self.remaining.len();
+        if len == 0 {
+            return None;
+        }
+        let mid = ::core::cmp::min(self.chunk_size, len);
+        let (chunk, remaining) = take(&mut self.remaining).stackbox_split_at(mid);
+        self.remaining = remaining;
+        Some(chunk)
+    }
+
+    #[inline]
+    fn size_hint (self: &'_ Chunks<'frame, Item>)
+      -> (usize, Option<usize>)
+    {
+        let len = self.remaining.len();
+        let count =
+            len / self.chunk_size
+            + if len % self.chunk_size != 0 { 1 } else { 0 }
+        ;
+        (count, Some(count))
     }
 }
 
+impl<'frame, Item : 'frame> ExactSizeIterator for Chunks<'frame, Item> {}
+
+/// Owned iterator over `chunk_size`-long subslices, starting from the end.
+///
+/// See [`StackBox::stackbox_rchunks`].
+pub
+struct RChunks<'frame, Item : 'frame> (
+    Chunks<'frame, Item>,
+);
+
+impl<'frame, Item : 'frame> RChunks<'frame, Item> {
+    pub(in super)
+    fn new (slice: StackBox<'frame, [Item]>, chunk_size: usize)
+      -> Self
+    {
+        RChunks(Chunks::new(slice, chunk_size))
+    }
+}
+
+impl<'frame, Item : 'frame> Iterator for RChunks<'frame, Item> {
+    type Item = StackBox<'frame, [Item]>;
+
+    #[inline]
+    fn next (self: &'_ mut RChunks<'frame, Item>)
+      -> Option<StackBox<'frame, [Item]>>
+    {
+        let Chunks { remaining, chunk_size } = &mut self.0;
+        let len = remaining.len();
+        if len == 0 {
+            return None;
+        }
+        let mid = len - ::core::cmp::min(*chunk_size, len);
+        let (hd, chunk) = take(remaining).stackbox_split_at(mid);
+        *remaining = hd;
+        Some(chunk)
+    }
+
+    #[inline]
+    fn size_hint (self: &'_ RChunks<'frame, Item>)
+      -> (usize, Option<usize>)
+    {
+        self.0.size_hint()
+    }
+}
+
+impl<'frame, Item : 'frame> ExactSizeIterator for RChunks<'frame, Item> {}
+
+/// Owned iterator over exactly `chunk_size`-long subslices.
+///
+/// See [`StackBox::stackbox_chunks_exact`].
+pub
+struct ChunksExact<'frame, Item : 'frame> {
+    chunks: Chunks<'frame, Item>,
+    remainder: StackBox<'frame, [Item]>,
+}
+
+impl<'frame, Item : 'frame> ChunksExact<'frame, Item> {
+    pub(in super)
+    fn new (slice: StackBox<'frame, [Item]>, chunk_size: usize)
+      -> Self
+    {
+        assert!(chunk_size != 0, "chunk size must be non-zero");
+        let mid = slice.len() - slice.len() % chunk_size;
+        let (hd, remainder) = slice.stackbox_split_at(mid);
+        ChunksExact {
+            chunks: Chunks::new(hd, chunk_size),
+            remainder,
+        }
+    }
+
+    /// The items which do not fit in a chunk (fewer than `chunk_size`).
+    #[inline]
+    pub
+    fn remainder (self: &'_ ChunksExact<'frame, Item>)
+      -> &'_ [Item]
+    {
+        &self.remainder
+    }
+
+    /// Yields ownership of the items which do not fit in a chunk, dropping
+    /// the chunks not yet iterated over.
+    #[inline]
+    pub
+    fn into_remainder (self: ChunksExact<'frame, Item>)
+      -> StackBox<'frame, [Item]>
+    {
+        self.remainder
+    }
+}
+
+impl<'frame, Item : 'frame> Iterator for ChunksExact<'frame, Item> {
+    type Item = StackBox<'frame, [Item]>;
+
+    #[inline]
+    fn next (self: &'_ mut ChunksExact<'frame, Item>)
+      -> Option<StackBox<'frame, [Item]>>
+    {
+        self.chunks.next()
+    }
+
+    #[inline]
+    fn size_hint (self: &'_ ChunksExact<'frame, Item>)
+      -> (usize, Option<usize>)
+    {
+        self.chunks.size_hint()
+    }
+}
+
+impl<'frame, Item : 'frame> ExactSizeIterator for ChunksExact<'frame, Item> {}
+
 #[cfg(test)]
 mod tests {
     #[test]
@@ -46,4 +369,101 @@ mod tests {
             drop::<String>(s);
         }
     }
+
+    #[test]
+    fn double_ended ()
+    {
+        use ::stackbox::prelude::*;
+
+        let rc = ::std::rc::Rc::new(());
+        let count = || ::std::rc::Rc::strong_count(&rc);
+        let rc = || rc.clone();
+
+        stackbox!(let slice: StackBox<'_, [_]> = [
+            (0, rc()), (1, rc()), (2, rc()), (3, rc()), (4, rc()),
+        ]);
+        let mut iter = slice.into_iter();
+        assert_eq!(iter.len(), 5);
+        assert_eq!(iter.next().unwrap().0, 0);
+        assert_eq!(iter.next_back().unwrap().0, 4);
+        assert_eq!(count(), 4);
+        assert_eq!(iter.as_slice().len(), 3);
+        iter.as_mut_slice()[0].0 = 42;
+        assert!(format!("{:?}", iter).starts_with("Iter([(42, "));
+        assert_eq!(iter.next_back().unwrap().0, 3);
+        let remaining = iter.into_stackbox();
+        assert_eq!(remaining.iter().map(|&(x, _)| x).collect::<Vec<_>>(), [42, 2]);
+        assert_eq!(count(), 3);
+        let mut iter = remaining.into_iter();
+        assert_eq!(iter.next().unwrap().0, 42);
+        drop(iter);
+        assert_eq!(count(), 1);
+    }
+
+    #[test]
+    fn chunks ()
+    {
+        use ::stackbox::prelude::*;
+
+        let lens = |iter: &mut dyn Iterator<Item = StackBox<'_, [i32]>>| {
+            iter.map(|it| it.len()).collect::<Vec<_>>()
+        };
+        stackbox!(let slice: StackBox<'_, [_]> = [1, 2, 3, 4, 5]);
+        let mut chunks = slice.stackbox_chunks(2);
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(lens(&mut chunks), [2, 2, 1]);
+
+        stackbox!(let slice: StackBox<'_, [_]> = [1, 2, 3, 4, 5]);
+        let mut rchunks = slice.stackbox_rchunks(2);
+        assert_eq!(*rchunks.next().unwrap(), [4, 5]);
+        assert_eq!(*rchunks.next().unwrap(), [2, 3]);
+        assert_eq!(*rchunks.next().unwrap(), [1]);
+        assert!(rchunks.next().is_none());
+
+        stackbox!(let slice: StackBox<'_, [_]> = [1, 2, 3, 4, 5]);
+        let mut chunks_exact = slice.stackbox_chunks_exact(2);
+        assert_eq!(chunks_exact.remainder(), [5]);
+        assert_eq!(lens(&mut chunks_exact), [2, 2]);
+        assert_eq!(*chunks_exact.into_remainder(), [5]);
+    }
+
+    #[test]
+    fn split_and_join ()
+    {
+        use ::stackbox::prelude::*;
+
+        let rc = ::std::rc::Rc::new(());
+        let count = || ::std::rc::Rc::strong_count(&rc);
+        let rc = || rc.clone();
+
+        stackbox!(let slice: StackBox<'_, [_]> = [rc(), rc(), rc(), rc()]);
+        let (first, slice) = slice.stackbox_split_first().unwrap();
+        let (last, slice) = slice.stackbox_split_last().unwrap();
+        assert_eq!(slice.len(), 2);
+        drop(first);
+        assert_eq!(count(), 4);
+        let mut chunks = slice.stackbox_chunks(1);
+        let (a, b) = (chunks.next().unwrap(), chunks.next().unwrap());
+        let (b, a) = match unsafe { StackBox::stackbox_join(b, a) } {
+            | Ok(_) => unreachable!(),
+            | Err(it) => it,
+        };
+        let slice = unsafe { StackBox::stackbox_join(a, b) }.ok().unwrap();
+        assert_eq!(slice.len(), 2);
+        drop(slice);
+        assert_eq!(count(), 2);
+        drop(last);
+        assert_eq!(count(), 1);
+
+        stackbox!(let slice: StackBox<'_, [_]> = [rc(), rc(), rc()]);
+        let (pairs, remainder) = slice.into_array_chunks::<[_; 2]>();
+        assert_eq!((pairs.len(), remainder.len()), (1, 1));
+        drop(pairs);
+        assert_eq!(count(), 2);
+        drop(remainder);
+        assert_eq!(count(), 1);
+
+        stackbox!(let empty: StackBox<'_, [()]> = []);
+        assert!(empty.stackbox_split_last().is_none());
+    }
 }
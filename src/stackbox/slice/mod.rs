@@ -3,6 +3,9 @@ use super::*;
 pub
 mod iter;
 
+pub use vec::StackVec;
+mod vec;
+
 impl<'frame, Item : 'frame> StackBox<'frame, [Item]> {
     /// # Safety
     ///
@@ -20,23 +23,207 @@ impl<'frame, Item : 'frame> StackBox<'frame, [Item]> {
     }
 
     /// [`Vec`]-like behavior for [`StackBox`]: pop its first item.
+    ///
+    /// Same as [`.pop_front()`][`StackBox::pop_front`].
+    #[inline]
     pub
     fn stackbox_pop (self: &'_ mut StackBox<'frame, [Item]>)
       -> Option<Item>
     {
-        if self.is_empty() {
+        self.pop_front()
+    }
+
+    /// Shrinks the owned slice, in place, to its `start .. start + len`
+    /// subslice, without dropping anything.
+    ///
+    /// # Safety
+    ///
+    /// The subslice must be in bounds, and the items outside of it must be
+    /// considered as moved out (they are leaked otherwise).
+    #[inline]
+    unsafe
+    fn shrink_to (
+        self: &'_ mut StackBox<'frame, [Item]>,
+        start: usize,
+        len: usize,
+    )
+    {
+        let ptr = self.as_mut_ptr().add(start);
+        ::core::ptr::write(self, StackBox::from_raw(
+            ::core::ptr::slice_from_raw_parts_mut(ptr, len)
+        ));
+    }
+
+    /// Removes the first item and returns it, if any.
+    #[inline]
+    pub
+    fn pop_front (self: &'_ mut StackBox<'frame, [Item]>)
+      -> Option<Item>
+    {
+        let len = self.len();
+        if len == 0 {
+            return None;
+        }
+        unsafe {
+            // Safety: the first item is moved out.
+            let item = self.as_ptr().read();
+            self.shrink_to(1, len - 1);
+            Some(item)
+        }
+    }
+
+    /// Removes the last item and returns it, if any.
+    #[inline]
+    pub
+    fn pop_back (self: &'_ mut StackBox<'frame, [Item]>)
+      -> Option<Item>
+    {
+        let len = self.len();
+        if len == 0 {
             return None;
         }
-        let placeholder = unsafe {
-            // Safety: empty slice.
-            StackBox::assume_owns_all(&mut [])
-        };
-        let this = ::core::mem::replace(self, placeholder);
-        let (hd, tl) = this.stackbox_split_at(1);
-        *self = tl;
-        Some(unsafe {
-            ::core::ptr::read(&ManuallyDrop::new(hd)[0])
-        })
+        unsafe {
+            // Safety: the last item is moved out.
+            let item = self.as_ptr().add(len - 1).read();
+            self.shrink_to(0, len - 1);
+            Some(item)
+        }
+    }
+
+    /// Keeps the first `len` items, dropping the others.
+    ///
+    /// It has no effect if `len` is greater than the current length.
+    pub
+    fn truncate (self: &'_ mut StackBox<'frame, [Item]>, len: usize)
+    {
+        let old_len = self.len();
+        if len >= old_len {
+            return;
+        }
+        unsafe {
+            // Safety: the tail is dropped right afterwards (should that
+            // panic, the remaining items of the tail would just be leaked).
+            let tail = ::core::ptr::slice_from_raw_parts_mut(
+                self.as_mut_ptr().add(len),
+                old_len - len,
+            );
+            self.shrink_to(0, len);
+            ::core::ptr::drop_in_place(tail);
+        }
+    }
+
+    /// Removes and returns the item at position `index`, replacing it with
+    /// the last item.
+    ///
+    /// ### Panics
+    ///
+    /// If `index >= len`.
+    pub
+    fn swap_remove (self: &'_ mut StackBox<'frame, [Item]>, index: usize)
+      -> Item
+    {
+        let len = self.len();
+        assert!(index < len, "`swap_remove`: index out of bounds");
+        unsafe {
+            // Safety: the item is moved out, and the last one is moved in its
+            // stead.
+            let base = self.as_mut_ptr();
+            let item = base.add(index).read();
+            ::core::ptr::copy(base.add(len - 1), base.add(index), 1);
+            self.shrink_to(0, len - 1);
+            item
+        }
+    }
+
+    /// Removes and returns the item at position `index`, shifting all the
+    /// items after it to the left.
+    ///
+    /// ### Panics
+    ///
+    /// If `index >= len`.
+    pub
+    fn remove (self: &'_ mut StackBox<'frame, [Item]>, index: usize)
+      -> Item
+    {
+        let len = self.len();
+        assert!(index < len, "`remove`: index out of bounds");
+        unsafe {
+            // Safety: the item is moved out, and the tail is shifted over it.
+            let at = self.as_mut_ptr().add(index);
+            let item = at.read();
+            ::core::ptr::copy(at.add(1), at, len - index - 1);
+            self.shrink_to(0, len - 1);
+            item
+        }
+    }
+
+    /// Keeps only the items for which the predicate returns `true`, in
+    /// order, dropping the other ones.
+    pub
+    fn retain (
+        self: &'_ mut StackBox<'frame, [Item]>,
+        mut predicate: impl FnMut(&'_ Item) -> bool,
+    )
+    {
+        let mut compactor = Compactor::new(self, 0);
+        while compactor.read < compactor.len {
+            unsafe {
+                let cur = compactor.base.add(compactor.read);
+                if predicate(&*cur) {
+                    compactor.keep();
+                } else {
+                    compactor.read += 1;
+                    ::core::ptr::drop_in_place(cur);
+                }
+            }
+        }
+    }
+
+    /// Removes consecutive items for which `same_bucket(item, previous)`
+    /// returns `true`, dropping them.
+    pub
+    fn dedup_by (
+        self: &'_ mut StackBox<'frame, [Item]>,
+        mut same_bucket: impl FnMut(&'_ mut Item, &'_ mut Item) -> bool,
+    )
+    {
+        if self.len() <= 1 {
+            return;
+        }
+        let mut compactor = Compactor::new(self, 1);
+        while compactor.read < compactor.len {
+            unsafe {
+                let cur = compactor.base.add(compactor.read);
+                let prev = compactor.base.add(compactor.write - 1);
+                if same_bucket(&mut *cur, &mut *prev) {
+                    compactor.read += 1;
+                    ::core::ptr::drop_in_place(cur);
+                } else {
+                    compactor.keep();
+                }
+            }
+        }
+    }
+
+    /// Removes consecutive items that map to the same key, dropping them.
+    #[inline]
+    pub
+    fn dedup_by_key<Key : PartialEq> (
+        self: &'_ mut StackBox<'frame, [Item]>,
+        mut key: impl FnMut(&'_ mut Item) -> Key,
+    )
+    {
+        self.dedup_by(|a, b| key(a) == key(b))
+    }
+
+    /// Removes consecutive repeated items, dropping them.
+    #[inline]
+    pub
+    fn dedup (self: &'_ mut StackBox<'frame, [Item]>)
+    where
+        Item : PartialEq,
+    {
+        self.dedup_by(|a, b| a == b)
     }
 
     /// [`StackBox`] / owned equivalent of the `slice` splitting methods.
@@ -71,15 +258,267 @@ impl<'frame, Item : 'frame> StackBox<'frame, [Item]> {
             )
         }
     }
+
+    /// Owned equivalent of [`slice::split_first`]: splits off the first item,
+    /// if any, into its own [`StackBox`].
+    #[inline]
+    pub
+    fn stackbox_split_first (self: StackBox<'frame, [Item]>)
+      -> Option<(StackBox<'frame, Item>, StackBox<'frame, [Item]>)>
+    {
+        if self.is_empty() {
+            return None;
+        }
+        let (hd, tl) = self.stackbox_split_at(1);
+        let hd: *mut Item = hd.into_raw() as _;
+        Some((
+            unsafe {
+                // Safety: ownership of the single item is transferred.
+                StackBox::from_raw(hd)
+            },
+            tl,
+        ))
+    }
+
+    /// Owned equivalent of [`slice::split_last`]: splits off the last item,
+    /// if any, into its own [`StackBox`].
+    #[inline]
+    pub
+    fn stackbox_split_last (self: StackBox<'frame, [Item]>)
+      -> Option<(StackBox<'frame, Item>, StackBox<'frame, [Item]>)>
+    {
+        let mid = self.len().checked_sub(1)?;
+        let (hd, tl) = self.stackbox_split_at(mid);
+        let tl: *mut Item = tl.into_raw() as _;
+        Some((
+            unsafe {
+                // Safety: ownership of the single item is transferred.
+                StackBox::from_raw(tl)
+            },
+            hd,
+        ))
+    }
+
+    /// Owned equivalent of [`slice::chunks`]: an iterator over owned
+    /// `chunk_size`-long subslices (but for the last one, which may be
+    /// shorter).
+    ///
+    /// ### Panics
+    ///
+    /// If `chunk_size` is `0`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ::stackbox::prelude::*;
+    ///
+    /// stackbox!(let work: StackBox<'_, [_]> = [
+    ///     String::from("a"), "b".into(), "c".into(), "d".into(), "e".into(),
+    /// ]);
+    /// let mut batches = work.stackbox_chunks(2);
+    /// let batch: StackBox<'_, [String]> = batches.next().unwrap();
+    /// assert_eq!(*batch, ["a", "b"]);
+    /// assert_eq!(batches.map(|it| it.len()).collect::<Vec<_>>(), [2, 1]);
+    /// ```
+    #[inline]
+    pub
+    fn stackbox_chunks (self: StackBox<'frame, [Item]>, chunk_size: usize)
+      -> iter::Chunks<'frame, Item>
+    {
+        iter::Chunks::new(self, chunk_size)
+    }
+
+    /// Owned equivalent of [`slice::rchunks`]: an iterator over owned
+    /// `chunk_size`-long subslices, starting from the end (the last yielded
+    /// one may be shorter).
+    ///
+    /// ### Panics
+    ///
+    /// If `chunk_size` is `0`.
+    #[inline]
+    pub
+    fn stackbox_rchunks (self: StackBox<'frame, [Item]>, chunk_size: usize)
+      -> iter::RChunks<'frame, Item>
+    {
+        iter::RChunks::new(self, chunk_size)
+    }
+
+    /// Owned equivalent of [`slice::chunks_exact`]: an iterator over owned
+    /// `chunk_size`-long subslices. The remaining items can be recovered with
+    /// [`.into_remainder()`][`iter::ChunksExact::into_remainder`].
+    ///
+    /// ### Panics
+    ///
+    /// If `chunk_size` is `0`.
+    #[inline]
+    pub
+    fn stackbox_chunks_exact (
+        self: StackBox<'frame, [Item]>,
+        chunk_size: usize,
+    ) -> iter::ChunksExact<'frame, Item>
+    {
+        iter::ChunksExact::new(self, chunk_size)
+    }
+
+    /// Splits the owned slice into owned `[Item; N]` chunks, and the owned
+    /// remainder (of length `< N`).
+    ///
+    /// `Array = [Item; N]`, with the same [requirements regarding
+    /// `N`][`StackBox::into_slice`].
+    ///
+    /// ### Panics
+    ///
+    /// If `N` is `0`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ::stackbox::prelude::*;
+    ///
+    /// stackbox!(let slice: StackBox<'_, [_]> = [1, 2, 3, 4, 5]);
+    /// let (pairs, remainder) = slice.into_array_chunks::<[_; 2]>();
+    /// assert_eq!(*pairs, [[1, 2], [3, 4]]);
+    /// assert_eq!(*remainder, [5]);
+    /// ```
+    #[inline]
+    pub
+    fn into_array_chunks<Array : IsArray<'frame, Item = Item>> (
+        self: StackBox<'frame, [Item]>,
+    ) -> (StackBox<'frame, [Array]>, StackBox<'frame, [Item]>)
+    {
+        assert!(Array::LEN != 0, "`into_array_chunks`: `N` cannot be `0`");
+        let count = self.len() / Array::LEN;
+        let (chunks, remainder) = self.stackbox_split_at(count * Array::LEN);
+        let ptr: *mut Array = chunks.into_raw() as *mut Item as _;
+        (
+            unsafe {
+                // Safety: `[Item; N]` has the layout of `N` consecutive
+                // `Item`s, and ownership is transferred.
+                StackBox::from_raw(
+                    ::core::ptr::slice_from_raw_parts_mut(ptr, count)
+                )
+            },
+            remainder,
+        )
+    }
+
+    /// Merges back two owned slices which are contiguous, _i.e._, `tl`
+    /// starts right where `hd` ends. Otherwise, they are given back.
+    ///
+    /// # Safety
+    ///
+    /// Both slices must originate from the same owned slice (_e.g._, they
+    /// have been obtained through [`.stackbox_split_at()`][
+    /// `StackBox::stackbox_split_at`] or the chunking methods): two distinct
+    /// `StackBox`es which _happen_ to be contiguous in memory (_e.g._, with
+    /// adjacent [`Slot`]s) cannot be merged.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ::stackbox::prelude::*;
+    ///
+    /// stackbox!(let slice: StackBox<'_, [_]> = [1, 2, 3, 4]);
+    /// let (hd, tl) = slice.stackbox_split_at(1);
+    /// let slice = unsafe { StackBox::stackbox_join(hd, tl) }.ok().unwrap();
+    /// assert_eq!(*slice, [1, 2, 3, 4]);
+    /// ```
+    #[inline]
+    pub
+    unsafe
+    fn stackbox_join (
+        hd: StackBox<'frame, [Item]>,
+        tl: StackBox<'frame, [Item]>,
+    ) -> Result<
+            StackBox<'frame, [Item]>,
+            (StackBox<'frame, [Item]>, StackBox<'frame, [Item]>),
+        >
+    {
+        let len = hd.len() + tl.len();
+        if hd.as_ptr().wrapping_add(hd.len()) != tl.as_ptr() {
+            return Err((hd, tl));
+        }
+        let ptr: *mut Item = hd.into_raw() as _;
+        let _: *mut [Item] = tl.into_raw();
+        Ok(StackBox::from_raw(::core::ptr::slice_from_raw_parts_mut(ptr, len)))
+    }
+}
+
+/// In-place filtering helper: the items in `write .. read` have been moved
+/// out or dropped, and on drop (including on unwind), the `read .. len`
+/// tail is shifted over that gap.
+struct Compactor<'borrow, 'frame, Item : 'frame> {
+    this: &'borrow mut StackBox<'frame, [Item]>,
+    base: *mut Item,
+    len: usize,
+    read: usize,
+    write: usize,
+}
+
+impl<'borrow, 'frame, Item : 'frame> Compactor<'borrow, 'frame, Item> {
+    #[inline]
+    fn new (this: &'borrow mut StackBox<'frame, [Item]>, start: usize)
+      -> Self
+    {
+        Compactor {
+            base: this.as_mut_ptr(),
+            len: this.len(),
+            this,
+            read: start,
+            write: start,
+        }
+    }
+
+    /// Moves the item at `read` to `write`.
+    #[inline]
+    unsafe
+    fn keep (self: &'_ mut Self)
+    {
+        if self.read != self.write {
+            ::core::ptr::copy_nonoverlapping(
+                self.base.add(self.read),
+                self.base.add(self.write),
+                1,
+            );
+        }
+        self.read += 1;
+        self.write += 1;
+    }
+}
+
+impl<Item> Drop for Compactor<'_, '_, Item> {
+    fn drop (self: &'_ mut Self)
+    {
+        let tail_len = self.len - self.read;
+        unsafe {
+            // Safety: the gap is only made of moved out or dropped items.
+            ::core::ptr::copy(
+                self.base.add(self.read),
+                self.base.add(self.write),
+                tail_len,
+            );
+            self.this.shrink_to(0, self.write + tail_len);
+        }
+    }
 }
 
 pub
 trait IsArray<'frame> : 'frame {
     type Item : 'frame;
 
+    /// The `N` in `[Item; N]`.
+    const LEN : usize;
+
+    /// `[StackBox<'frame, Item>; N]`.
+    type Boxes : 'frame;
+
     fn into_slice (this: StackBox<'frame, Self>)
       -> StackBox<'frame, [Self::Item]>
     ;
+
+    fn into_boxes (this: StackBox<'frame, Self>)
+      -> Self::Boxes
+    ;
 }
 
 /// `Array = [Array::Item; N]`.
@@ -88,7 +527,9 @@ impl<'frame, Array : IsArray<'frame>> StackBox<'frame, Array> {
     ///
     /// ### Requirements
     ///
-    ///   - Either the `"const-generics"` feature needs to be enabled,
+    ///   - Either the compiler needs to be `1.51.0` or newer (this is
+    ///     automatically detected), or, on an older nightly, the
+    ///     `"const-generics"` feature needs to be enabled,
     ///
     ///   - Or `N` must be one of the hard-coded ones:
     ///
@@ -110,6 +551,22 @@ impl<'frame, Array : IsArray<'frame>> StackBox<'frame, Array> {
     ///     ]);
     ///     let _: String = boxed_slice.stackbox_pop().unwrap();
     ///     ```
+    ///
+    /// ### Reverse conversion
+    ///
+    /// Going back from a `StackBox<[T]>` to a `StackBox<[T; N]>` is done
+    /// through [`TryFrom`][`::core::convert::TryFrom`], which gives the
+    /// `StackBox` back on length mismatch:
+    ///
+    /// ```rust
+    /// use ::core::convert::TryFrom;
+    /// use ::stackbox::prelude::*;
+    ///
+    /// stackbox!(let slice: StackBox<'_, [_]> = [1, 2, 3]);
+    /// let slice = StackBox::<[_; 2]>::try_from(slice).unwrap_err();
+    /// let array = StackBox::<[_; 3]>::try_from(slice).unwrap();
+    /// assert_eq!(array.into_array(), [1, 2, 3]);
+    /// ```
     #[inline]
     pub
     fn into_slice (self: StackBox<'frame, Array>)
@@ -117,6 +574,40 @@ impl<'frame, Array : IsArray<'frame>> StackBox<'frame, Array> {
     {
         IsArray::into_slice(self)
     }
+
+    /// Splits a `StackBox<[T; N]>` into `N` `StackBox<T>`s, one per element,
+    /// so that each element can be owned individually.
+    ///
+    /// The same [requirements regarding `N`][`StackBox::into_slice`] apply.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ::stackbox::prelude::*;
+    ///
+    /// stackbox!(let array = [String::from("a"), "b".into()]);
+    /// let [a, b]: [StackBox<'_, String>; 2] = array.into_boxes();
+    /// drop(a);
+    /// assert_eq!(*b, "b");
+    /// ```
+    #[inline]
+    pub
+    fn into_boxes (self: StackBox<'frame, Array>)
+      -> Array::Boxes
+    {
+        IsArray::into_boxes(self)
+    }
+
+    /// Moves the array out of the [`StackBox`], by value.
+    ///
+    /// Same as [`StackBox::into_inner`].
+    #[inline]
+    pub
+    fn into_array (self: StackBox<'frame, Array>)
+      -> Array
+    {
+        self.into_inner()
+    }
 }
 
 macro_rules! impl_for_Ns {(
@@ -133,6 +624,10 @@ macro_rules! impl_for_Ns {(
         {
             type Item = Item;
 
+            const LEN : usize = $N;
+
+            type Boxes = [StackBox<'frame, Item>; $N];
+
             #[inline]
             fn into_slice (this: StackBox<'frame, [Item; $N]>)
               -> StackBox<'frame, [Item]>
@@ -152,18 +647,62 @@ macro_rules! impl_for_Ns {(
                     }
                 }
             }
+
+            #[inline]
+            fn into_boxes (this: StackBox<'frame, [Item; $N]>)
+              -> [StackBox<'frame, Item>; $N]
+            {
+                let ptr: *mut Item = this.into_raw() as _;
+                let mut boxes =
+                    ::core::mem::MaybeUninit::<[StackBox<'frame, Item>; $N]>
+                        ::uninit()
+                ;
+                let out: *mut StackBox<'frame, Item> = boxes.as_mut_ptr().cast();
+                unsafe {
+                    // Safety: ownership of each (disjoint) element is
+                    // transferred, and all the `StackBox`es are initialized.
+                    for i in 0 .. $N {
+                        out.add(i).write(StackBox::from_raw(ptr.add(i)));
+                    }
+                    boxes.assume_init()
+                }
+            }
+        }
+
+        /// Owned equivalent of the `&mut [T] → &mut [T; N]` conversion.
+        impl<'frame, Item : 'frame $(, $($generics)*)?>
+            ::core::convert::TryFrom<StackBox<'frame, [Item]>>
+        for
+            StackBox<'frame, [Item; $N]>
+        {
+            type Error = StackBox<'frame, [Item]>;
+
+            #[inline]
+            fn try_from (slice: StackBox<'frame, [Item]>)
+              -> Result<Self, StackBox<'frame, [Item]>>
+            {
+                if slice.len() != $N {
+                    return Err(slice);
+                }
+                let ptr: *mut [Item; $N] = slice.into_raw() as *mut Item as _;
+                Ok(unsafe {
+                    // Safety: `[Item; N]` has the layout of `N` consecutive
+                    // `Item`s, and ownership is transferred.
+                    StackBox::from_raw(ptr)
+                })
+            }
         }
     )+
 )}
 
-#[cfg(feature = "const-generics")]
+#[cfg(any(stackbox_const_generics, feature = "const-generics"))]
 const _: () = {
     impl_for_Ns! {
         @for [const N: usize] N
     }
 };
 
-#[cfg(not(feature = "const-generics"))]
+#[cfg(not(any(stackbox_const_generics, feature = "const-generics")))]
 const _: () = {
     impl_for_Ns! {
         /* Is this a drawing of a flag? */
@@ -198,3 +737,117 @@ const _: () = {
       4096,
     }
 };
+
+#[cfg(test)]
+mod tests {
+    use ::stackbox::prelude::*;
+
+    #[test]
+    fn vec_like_shrinking ()
+    {
+        stackbox!(let mut slice: StackBox<'_, [_]> = [1, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(slice.pop_front(), Some(1));
+        assert_eq!(slice.pop_back(), Some(7));
+        assert_eq!(*slice, [2, 3, 4, 5, 6]);
+        assert_eq!(slice.swap_remove(0), 2);
+        assert_eq!(*slice, [6, 3, 4, 5]);
+        assert_eq!(slice.remove(1), 3);
+        assert_eq!(*slice, [6, 4, 5]);
+        slice.retain(|&x| x != 4);
+        assert_eq!(*slice, [6, 5]);
+        slice.truncate(5);
+        slice.truncate(1);
+        assert_eq!(*slice, [6]);
+        assert_eq!(slice.pop_back(), Some(6));
+        assert_eq!(slice.pop_back(), None);
+        assert_eq!(slice.pop_front(), None);
+
+        stackbox!(let mut slice: StackBox<'_, [_]> = [1, 1, 2, 3, 3, 3, 1]);
+        slice.dedup();
+        assert_eq!(*slice, [1, 2, 3, 1]);
+        slice.dedup_by_key(|x| *x / 2);
+        assert_eq!(*slice, [1, 2, 1]);
+    }
+
+    #[test]
+    fn test_drops ()
+    {
+        let rc = ::std::rc::Rc::new(());
+        let count = || ::std::rc::Rc::strong_count(&rc);
+        let rc = || rc.clone();
+
+        stackbox!(let mut slice: StackBox<'_, [_]> = [
+            (0, rc()), (1, rc()), (1, rc()), (2, rc()), (3, rc()), (4, rc()),
+        ]);
+        assert_eq!(count(), 7);
+        slice.dedup_by_key(|&mut (x, _)| x);
+        assert_eq!(count(), 6);
+        slice.retain(|&(x, _)| x % 2 == 0);
+        assert_eq!(count(), 4);
+        drop(slice.remove(0));
+        assert_eq!(count(), 3);
+        slice.truncate(1);
+        assert_eq!(count(), 2);
+        drop(slice);
+        assert_eq!(count(), 1);
+    }
+
+    #[test]
+    fn arrays ()
+    {
+        use ::core::convert::TryFrom;
+
+        let rc = ::std::rc::Rc::new(());
+        let count = || ::std::rc::Rc::strong_count(&rc);
+        let rc = || rc.clone();
+
+        stackbox!(let slice: StackBox<'_, [_]> = [rc(), rc()]);
+        let slice = StackBox::<[_; 3]>::try_from(slice).unwrap_err();
+        let array = StackBox::<[_; 2]>::try_from(slice).unwrap();
+        assert_eq!(count(), 3);
+        let [a, b] = array.into_boxes();
+        drop(a);
+        assert_eq!(count(), 2);
+        drop(b);
+        assert_eq!(count(), 1);
+
+        stackbox!(let empty = [(); 0]);
+        let [] = empty.into_boxes();
+    }
+
+    #[cfg(stackbox_const_generics)]
+    #[test]
+    fn arbitrary_lengths ()
+    {
+        mk_slots!(a, b);
+        let slice = StackBox::new_in(a, [0_u8; 37]).into_slice();
+        assert_eq!(slice.len(), 37);
+        let slice = StackBox::new_in(b, [0_u8; 3000]).into_slice();
+        assert_eq!(slice.len(), 3000);
+        let (chunks, _) = slice.into_array_chunks::<[_; 37]>();
+        assert_eq!(chunks.len(), 3000 / 37);
+    }
+
+    #[test]
+    fn retain_unwind ()
+    {
+        let rc = ::std::rc::Rc::new(());
+        let count = || ::std::rc::Rc::strong_count(&rc);
+        let rc = || rc.clone();
+
+        stackbox!(let mut slice: StackBox<'_, [_]> = [
+            (0, rc()), (1, rc()), (2, rc()), (3, rc()),
+        ]);
+        let caught = ::std::panic::catch_unwind(
+            ::std::panic::AssertUnwindSafe(|| slice.retain(|&(x, _)| {
+                assert!(x < 2);
+                x != 1
+            }))
+        );
+        assert!(caught.is_err());
+        assert_eq!(slice.iter().map(|&(x, _)| x).collect::<Vec<_>>(), [0, 2, 3]);
+        assert_eq!(count(), 4);
+        drop(slice);
+        assert_eq!(count(), 1);
+    }
+}
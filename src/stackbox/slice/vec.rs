@@ -0,0 +1,332 @@
+use super::*;
+
+/// A fixed-capacity `Vec`, whose (inline) backing storage is a [`Slot`] of
+/// an array: `Array = [Item; N]`.
+///
+/// Once all the wanted items have been pushed, it can be frozen into an owned
+/// slice, _i.e._, a [`StackBox`]`<'frame, [Item]>`, of exactly the pushed
+/// length, through [`.into_stackbox()`][`StackVec::into_stackbox`].
+///
+/// The same [requirements regarding `N`][`StackBox::into_slice`] apply.
+///
+/// ## Example
+///
+/// ```rust
+/// use ::stackbox::prelude::*;
+///
+/// mk_slots!(storage);
+/// let mut vec = StackVec::<[String; 8]>::new_in(storage);
+/// for s in "Hello, World!".split(' ') {
+///     vec.push(s.into());
+/// }
+/// assert_eq!(vec.capacity(), 8);
+/// let strings: StackBox<'_, [String]> = vec.into_stackbox();
+/// assert_eq!(strings.len(), 2);
+/// assert_eq!(*strings, ["Hello,", "World!"]);
+/// ```
+pub
+struct StackVec<'frame, Array : IsArray<'frame>> {
+    /// Invariant: the first `len` items are initialized.
+    storage: &'frame mut Slot<Array>,
+    len: usize,
+}
+
+impl<'frame, Array : IsArray<'frame>> StackVec<'frame, Array> {
+    /// Creates an empty `StackVec`, backed by the given [`Slot`].
+    #[inline]
+    pub
+    fn new_in (storage: &'frame mut Slot<Array>)
+      -> StackVec<'frame, Array>
+    {
+        StackVec { storage, len: 0 }
+    }
+
+    #[inline]
+    pub
+    fn len (self: &'_ Self)
+      -> usize
+    {
+        self.len
+    }
+
+    #[inline]
+    pub
+    fn is_empty (self: &'_ Self)
+      -> bool
+    {
+        self.len == 0
+    }
+
+    /// The `N` in `Array = [Item; N]`.
+    #[inline]
+    pub
+    fn capacity (self: &'_ Self)
+      -> usize
+    {
+        Array::LEN
+    }
+
+    #[inline]
+    pub
+    fn is_full (self: &'_ Self)
+      -> bool
+    {
+        self.len == Array::LEN
+    }
+
+    #[inline]
+    fn as_mut_ptr (self: &'_ mut Self)
+      -> *mut Array::Item
+    {
+        self.storage.place.as_mut_ptr().cast()
+    }
+
+    /// Appends an item at the end, or gives it back if the `StackVec` is full.
+    #[inline]
+    pub
+    fn try_push (self: &'_ mut Self, item: Array::Item)
+      -> Result<(), Array::Item>
+    {
+        if self.is_full() {
+            return Err(item);
+        }
+        unsafe {
+            // Safety: `len < N`.
+            self.as_mut_ptr().add(self.len).write(item);
+        }
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Appends an item at the end.
+    ///
+    /// ### Panics
+    ///
+    /// If the `StackVec` is full.
+    #[inline]
+    pub
+    fn push (self: &'_ mut Self, item: Array::Item)
+    {
+        if self.try_push(item).is_err() {
+            panic!("`StackVec::push`: capacity ({}) exceeded", Array::LEN);
+        }
+    }
+
+    /// Removes the last item and returns it, if any.
+    #[inline]
+    pub
+    fn pop (self: &'_ mut Self)
+      -> Option<Array::Item>
+    {
+        if self.is_empty() {
+            return None;
+        }
+        self.len -= 1;
+        Some(unsafe {
+            // Safety: the item at `len` is no longer considered initialized.
+            self.as_mut_ptr().add(self.len).read()
+        })
+    }
+
+    /// Inserts an item at position `index`, shifting all the items after it
+    /// to the right.
+    ///
+    /// ### Panics
+    ///
+    /// If `index > len`, or if the `StackVec` is full.
+    pub
+    fn insert (self: &'_ mut Self, index: usize, item: Array::Item)
+    {
+        let len = self.len;
+        assert!(index <= len, "`StackVec::insert`: index out of bounds");
+        assert!(len < Array::LEN,
+            "`StackVec::insert`: capacity ({}) exceeded", Array::LEN,
+        );
+        unsafe {
+            // Safety: `len < N`, so there is room for one extra item.
+            let at = self.as_mut_ptr().add(index);
+            ::core::ptr::copy(at, at.add(1), len - index);
+            at.write(item);
+        }
+        self.len = len + 1;
+    }
+
+    /// Removes and returns the item at position `index`, shifting all the
+    /// items after it to the left.
+    ///
+    /// ### Panics
+    ///
+    /// If `index >= len`.
+    pub
+    fn remove (self: &'_ mut Self, index: usize)
+      -> Array::Item
+    {
+        let len = self.len;
+        assert!(index < len, "`StackVec::remove`: index out of bounds");
+        unsafe {
+            // Safety: the item is read out before being overwritten.
+            let at = self.as_mut_ptr().add(index);
+            let item = at.read();
+            ::core::ptr::copy(at.add(1), at, len - index - 1);
+            self.len = len - 1;
+            item
+        }
+    }
+
+    /// Freezes the `StackVec` into an owned slice of its items.
+    #[inline]
+    pub
+    fn into_stackbox (self: StackVec<'frame, Array>)
+      -> StackBox<'frame, [Array::Item]>
+    {
+        let mut this = ManuallyDrop::new(self);
+        let len = this.len;
+        let ptr = this.as_mut_ptr();
+        unsafe {
+            // Safety: ownership of the `len` initialized items is transferred,
+            // and `storage` is borrowed for `'frame`.
+            StackBox::from_raw(::core::ptr::slice_from_raw_parts_mut(ptr, len))
+        }
+    }
+}
+
+impl<'frame, Array : IsArray<'frame>>
+    ::core::ops::Deref
+for
+    StackVec<'frame, Array>
+{
+    type Target = [Array::Item];
+
+    #[inline]
+    fn deref (self: &'_ StackVec<'frame, Array>)
+      -> &'_ [Array::Item]
+    {
+        unsafe {
+            // Safety: the first `len` items are initialized.
+            ::core::slice::from_raw_parts(
+                self.storage.place.as_ptr().cast(),
+                self.len,
+            )
+        }
+    }
+}
+
+impl<'frame, Array : IsArray<'frame>>
+    ::core::ops::DerefMut
+for
+    StackVec<'frame, Array>
+{
+    #[inline]
+    fn deref_mut (self: &'_ mut StackVec<'frame, Array>)
+      -> &'_ mut [Array::Item]
+    {
+        let len = self.len;
+        unsafe {
+            // Safety: the first `len` items are initialized.
+            ::core::slice::from_raw_parts_mut(self.as_mut_ptr(), len)
+        }
+    }
+}
+
+/// ### Panics
+///
+/// If the `StackVec`'s capacity is exceeded.
+impl<'frame, Array : IsArray<'frame>>
+    Extend<Array::Item>
+for
+    StackVec<'frame, Array>
+{
+    #[inline]
+    fn extend<Iterable : IntoIterator<Item = Array::Item>> (
+        self: &'_ mut StackVec<'frame, Array>,
+        iterable: Iterable,
+    )
+    {
+        iterable.into_iter().for_each(|item| self.push(item));
+    }
+}
+
+impl<'frame, Array : IsArray<'frame>>
+    ::core::fmt::Debug
+for
+    StackVec<'frame, Array>
+where
+    Array::Item : ::core::fmt::Debug,
+{
+    fn fmt (self: &'_ Self, f: &'_ mut ::core::fmt::Formatter<'_>)
+      -> ::core::fmt::Result
+    {
+        <[Array::Item] as ::core::fmt::Debug>::fmt(self, f)
+    }
+}
+
+impl<'frame, Array : IsArray<'frame>> Drop for StackVec<'frame, Array> {
+    #[inline]
+    fn drop (self: &'_ mut Self)
+    {
+        unsafe {
+            // Safety: the first `len` items are initialized.
+            ::core::ptr::drop_in_place::<[Array::Item]>(&mut **self)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::stackbox::prelude::*;
+
+    #[test]
+    fn vec_ops ()
+    {
+        mk_slots!(storage);
+        let mut vec = StackVec::<[String; 4]>::new_in(storage);
+        assert!(vec.is_empty());
+        vec.push("b".into());
+        vec.insert(0, "a".into());
+        vec.insert(2, "d".into());
+        vec.insert(2, "c".into());
+        assert!(vec.is_full());
+        assert_eq!(vec.try_push("e".into()), Err("e".into()));
+        assert_eq!(*vec, ["a", "b", "c", "d"]);
+        assert_eq!(vec.remove(1), "b");
+        assert_eq!(vec.pop().unwrap(), "d");
+        vec.extend(vec!["x".into()]);
+        assert_eq!(format!("{:?}", vec), r#"["a", "c", "x"]"#);
+        let boxed: StackBox<'_, [String]> = vec.into_stackbox();
+        assert_eq!(*boxed, ["a", "c", "x"]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn push_overflow ()
+    {
+        mk_slots!(storage);
+        let mut vec = StackVec::<[(); 1]>::new_in(storage);
+        vec.extend(vec![(), ()]);
+    }
+
+    #[test]
+    fn test_drops ()
+    {
+        let rc = ::std::rc::Rc::new(());
+        let count = || ::std::rc::Rc::strong_count(&rc);
+        let rc = || rc.clone();
+
+        mk_slots!(storage);
+        let mut vec = StackVec::<[_; 3]>::new_in(storage);
+        vec.extend(vec![rc(), rc()]);
+        assert_eq!(count(), 3);
+        drop(vec.pop());
+        assert_eq!(count(), 2);
+        drop(vec);
+        assert_eq!(count(), 1);
+
+        mk_slots!(storage);
+        let mut vec = StackVec::<[_; 3]>::new_in(storage);
+        vec.extend(vec![rc(), rc()]);
+        let boxed = vec.into_stackbox();
+        assert_eq!(count(), 3);
+        drop(boxed);
+        assert_eq!(count(), 1);
+    }
+}
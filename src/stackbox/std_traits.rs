@@ -0,0 +1,699 @@
+//! Forwarding implementations of the standard library traits, mirroring
+//! those of `Box`.
+
+use super::*;
+
+use ::core::{
+    borrow::{Borrow, BorrowMut},
+    cmp::Ordering,
+    fmt,
+    future::Future,
+    hash::{Hash, Hasher},
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+macro_rules! forward_fmt_traits {(
+    $($Trait:ident),* $(,)?
+) => (
+    $(
+        impl<'frame, T : ?Sized + 'frame>
+            fmt::$Trait
+        for
+            StackBox<'frame, T>
+        where
+            T : fmt::$Trait,
+        {
+            #[inline]
+            fn fmt (self: &'_ Self, f: &'_ mut fmt::Formatter<'_>)
+              -> fmt::Result
+            {
+                T::fmt(&**self, f)
+            }
+        }
+    )*
+)}
+
+forward_fmt_traits! {
+    Debug,
+    Display,
+}
+
+/// Displays the address of the pointee, like `Box` does.
+impl<'frame, T : ?Sized + 'frame>
+    fmt::Pointer
+for
+    StackBox<'frame, T>
+{
+    #[inline]
+    fn fmt (self: &'_ Self, f: &'_ mut fmt::Formatter<'_>)
+      -> fmt::Result
+    {
+        let ptr: *const T = &**self;
+        fmt::Pointer::fmt(&ptr, f)
+    }
+}
+
+impl<'frame, T : ?Sized + 'frame>
+    PartialEq
+for
+    StackBox<'frame, T>
+where
+    T : PartialEq,
+{
+    #[inline]
+    fn eq (self: &'_ Self, other: &'_ Self)
+      -> bool
+    {
+        T::eq(&**self, &**other)
+    }
+}
+
+impl<'frame, T : ?Sized + 'frame>
+    Eq
+for
+    StackBox<'frame, T>
+where
+    T : Eq,
+{}
+
+impl<'frame, T : ?Sized + 'frame>
+    PartialOrd
+for
+    StackBox<'frame, T>
+where
+    T : PartialOrd,
+{
+    #[inline]
+    fn partial_cmp (self: &'_ Self, other: &'_ Self)
+      -> Option<Ordering>
+    {
+        T::partial_cmp(&**self, &**other)
+    }
+
+    #[inline]
+    fn lt (self: &'_ Self, other: &'_ Self)
+      -> bool
+    {
+        T::lt(&**self, &**other)
+    }
+
+    #[inline]
+    fn le (self: &'_ Self, other: &'_ Self)
+      -> bool
+    {
+        T::le(&**self, &**other)
+    }
+
+    #[inline]
+    fn gt (self: &'_ Self, other: &'_ Self)
+      -> bool
+    {
+        T::gt(&**self, &**other)
+    }
+
+    #[inline]
+    fn ge (self: &'_ Self, other: &'_ Self)
+      -> bool
+    {
+        T::ge(&**self, &**other)
+    }
+}
+
+impl<'frame, T : ?Sized + 'frame>
+    Ord
+for
+    StackBox<'frame, T>
+where
+    T : Ord,
+{
+    #[inline]
+    fn cmp (self: &'_ Self, other: &'_ Self)
+      -> Ordering
+    {
+        T::cmp(&**self, &**other)
+    }
+}
+
+impl<'frame, T : ?Sized + 'frame>
+    Hash
+for
+    StackBox<'frame, T>
+where
+    T : Hash,
+{
+    #[inline]
+    fn hash<H : Hasher> (self: &'_ Self, state: &'_ mut H)
+    {
+        T::hash(&**self, state)
+    }
+}
+
+impl<'frame, T : ?Sized + 'frame>
+    Borrow<T>
+for
+    StackBox<'frame, T>
+{
+    #[inline]
+    fn borrow (self: &'_ Self)
+      -> &'_ T
+    {
+        &**self
+    }
+}
+
+impl<'frame, T : ?Sized + 'frame>
+    BorrowMut<T>
+for
+    StackBox<'frame, T>
+{
+    #[inline]
+    fn borrow_mut (self: &'_ mut Self)
+      -> &'_ mut T
+    {
+        &mut **self
+    }
+}
+
+impl<'frame, T : ?Sized + 'frame>
+    AsRef<T>
+for
+    StackBox<'frame, T>
+{
+    #[inline]
+    fn as_ref (self: &'_ Self)
+      -> &'_ T
+    {
+        &**self
+    }
+}
+
+impl<'frame, T : ?Sized + 'frame>
+    AsMut<T>
+for
+    StackBox<'frame, T>
+{
+    #[inline]
+    fn as_mut (self: &'_ mut Self)
+      -> &'_ mut T
+    {
+        &mut **self
+    }
+}
+
+/// Iterator traits.
+///
+/// Ideally these would be implemented for any `I : ?Sized + Iterator`, but that
+/// would overlap with the `IntoIterator` implementation of
+/// `StackBox<'_, [Item]>` (coherence has to assume that `[Item] : Iterator`
+/// could be implemented in the future). Hence the `Sized` implementors, plus
+/// the explicitly enumerated iterator trait objects.
+mod iterator_traits {
+    use super::*;
+
+    use ::core::iter::FusedIterator;
+
+    impl<'frame, I : 'frame>
+        Iterator
+    for
+        StackBox<'frame, I>
+    where
+        I : Iterator,
+    {
+        type Item = I::Item;
+
+        forward_iterator_methods!();
+    }
+
+    impl<'frame, I : 'frame>
+        DoubleEndedIterator
+    for
+        StackBox<'frame, I>
+    where
+        I : DoubleEndedIterator,
+    {
+        forward_double_ended_iterator_methods!();
+    }
+
+    impl<'frame, I : 'frame>
+        ExactSizeIterator
+    for
+        StackBox<'frame, I>
+    where
+        I : ExactSizeIterator,
+    {
+        forward_exact_size_iterator_methods!();
+    }
+
+    impl<'frame, I : 'frame>
+        FusedIterator
+    for
+        StackBox<'frame, I>
+    where
+        I : FusedIterator,
+    {}
+
+    impl_for_dyn_iterators! {
+        (),
+        (+ Send),
+        (+ Sync),
+        (+ Send + Sync),
+    }
+
+    macro_rules! impl_for_dyn_iterators {(
+        $(
+            ( $(+ $AutoTrait:ident)* )
+        ),* $(,)?
+    ) => (
+        $(
+            impl<'frame, 'iter : 'frame, Item>
+                Iterator
+            for
+                StackBox<'frame,
+                    dyn Iterator<Item = Item> $(+ $AutoTrait)* + 'iter,
+                >
+            {
+                type Item = Item;
+
+                forward_iterator_methods!();
+            }
+
+            impl<'frame, 'iter : 'frame, Item>
+                Iterator
+            for
+                StackBox<'frame,
+                    dyn DoubleEndedIterator<Item = Item> $(+ $AutoTrait)* + 'iter,
+                >
+            {
+                type Item = Item;
+
+                forward_iterator_methods!();
+            }
+
+            impl<'frame, 'iter : 'frame, Item>
+                DoubleEndedIterator
+            for
+                StackBox<'frame,
+                    dyn DoubleEndedIterator<Item = Item> $(+ $AutoTrait)* + 'iter,
+                >
+            {
+                forward_double_ended_iterator_methods!();
+            }
+
+            impl<'frame, 'iter : 'frame, Item>
+                Iterator
+            for
+                StackBox<'frame,
+                    dyn ExactSizeIterator<Item = Item> $(+ $AutoTrait)* + 'iter,
+                >
+            {
+                type Item = Item;
+
+                forward_iterator_methods!();
+            }
+
+            impl<'frame, 'iter : 'frame, Item>
+                ExactSizeIterator
+            for
+                StackBox<'frame,
+                    dyn ExactSizeIterator<Item = Item> $(+ $AutoTrait)* + 'iter,
+                >
+            {
+                forward_exact_size_iterator_methods!();
+            }
+        )*
+    )} use impl_for_dyn_iterators;
+
+    macro_rules! forward_iterator_methods {() => (
+        #[inline]
+        fn next (self: &'_ mut Self)
+          -> Option<Self::Item>
+        {
+            Iterator::next(&mut **self)
+        }
+
+        #[inline]
+        fn size_hint (self: &'_ Self)
+          -> (usize, Option<usize>)
+        {
+            Iterator::size_hint(&**self)
+        }
+
+        #[inline]
+        fn nth (self: &'_ mut Self, n: usize)
+          -> Option<Self::Item>
+        {
+            Iterator::nth(&mut **self, n)
+        }
+    )} use forward_iterator_methods;
+
+    macro_rules! forward_double_ended_iterator_methods {() => (
+        #[inline]
+        fn next_back (self: &'_ mut Self)
+          -> Option<Self::Item>
+        {
+            DoubleEndedIterator::next_back(&mut **self)
+        }
+
+        #[inline]
+        fn nth_back (self: &'_ mut Self, n: usize)
+          -> Option<Self::Item>
+        {
+            DoubleEndedIterator::nth_back(&mut **self, n)
+        }
+    )} use forward_double_ended_iterator_methods;
+
+    macro_rules! forward_exact_size_iterator_methods {() => (
+        #[inline]
+        fn len (self: &'_ Self)
+          -> usize
+        {
+            ExactSizeIterator::len(&**self)
+        }
+    )} use forward_exact_size_iterator_methods;
+}
+
+/// Allows `StackBox<'_, dyn Future<Output = …> + Unpin>` to be `.await`ed.
+impl<'frame, F : ?Sized + 'frame>
+    Future
+for
+    StackBox<'frame, F>
+where
+    F : Future + Unpin,
+{
+    type Output = F::Output;
+
+    #[inline]
+    fn poll (self: Pin<&'_ mut Self>, cx: &'_ mut Context<'_>)
+      -> Poll<F::Output>
+    {
+        F::poll(Pin::new(&mut **self.get_mut()), cx)
+    }
+}
+
+/// Like with `Box`, moving a `StackBox` never moves its pointee.
+impl<'frame, T : ?Sized + 'frame>
+    Unpin
+for
+    StackBox<'frame, T>
+{}
+
+/// `::std`-only traits.
+#[cfg(feature = "std")]
+mod std_only {
+    use super::*;
+
+    use ::std::{
+        error::Error,
+        io::{self, BufRead, IoSlice, IoSliceMut, Read, Seek, SeekFrom, Write},
+    };
+
+    impl<'frame, R : ?Sized + 'frame>
+        Read
+    for
+        StackBox<'frame, R>
+    where
+        R : Read,
+    {
+        #[inline]
+        fn read (self: &'_ mut Self, buf: &'_ mut [u8])
+          -> io::Result<usize>
+        {
+            R::read(&mut **self, buf)
+        }
+
+        #[inline]
+        fn read_vectored (self: &'_ mut Self, bufs: &'_ mut [IoSliceMut<'_>])
+          -> io::Result<usize>
+        {
+            R::read_vectored(&mut **self, bufs)
+        }
+
+        #[inline]
+        fn read_to_end (self: &'_ mut Self, buf: &'_ mut Vec<u8>)
+          -> io::Result<usize>
+        {
+            R::read_to_end(&mut **self, buf)
+        }
+
+        #[inline]
+        fn read_to_string (self: &'_ mut Self, buf: &'_ mut String)
+          -> io::Result<usize>
+        {
+            R::read_to_string(&mut **self, buf)
+        }
+
+        #[inline]
+        fn read_exact (self: &'_ mut Self, buf: &'_ mut [u8])
+          -> io::Result<()>
+        {
+            R::read_exact(&mut **self, buf)
+        }
+    }
+
+    impl<'frame, W : ?Sized + 'frame>
+        Write
+    for
+        StackBox<'frame, W>
+    where
+        W : Write,
+    {
+        #[inline]
+        fn write (self: &'_ mut Self, buf: &'_ [u8])
+          -> io::Result<usize>
+        {
+            W::write(&mut **self, buf)
+        }
+
+        #[inline]
+        fn write_vectored (self: &'_ mut Self, bufs: &'_ [IoSlice<'_>])
+          -> io::Result<usize>
+        {
+            W::write_vectored(&mut **self, bufs)
+        }
+
+        #[inline]
+        fn flush (self: &'_ mut Self)
+          -> io::Result<()>
+        {
+            W::flush(&mut **self)
+        }
+
+        #[inline]
+        fn write_all (self: &'_ mut Self, buf: &'_ [u8])
+          -> io::Result<()>
+        {
+            W::write_all(&mut **self, buf)
+        }
+
+        #[inline]
+        fn write_fmt (self: &'_ mut Self, fmt: fmt::Arguments<'_>)
+          -> io::Result<()>
+        {
+            W::write_fmt(&mut **self, fmt)
+        }
+    }
+
+    impl<'frame, B : ?Sized + 'frame>
+        BufRead
+    for
+        StackBox<'frame, B>
+    where
+        B : BufRead,
+    {
+        #[inline]
+        fn fill_buf (self: &'_ mut Self)
+          -> io::Result<&'_ [u8]>
+        {
+            B::fill_buf(&mut **self)
+        }
+
+        #[inline]
+        fn consume (self: &'_ mut Self, amt: usize)
+        {
+            B::consume(&mut **self, amt)
+        }
+
+        #[inline]
+        fn read_until (self: &'_ mut Self, byte: u8, buf: &'_ mut Vec<u8>)
+          -> io::Result<usize>
+        {
+            B::read_until(&mut **self, byte, buf)
+        }
+
+        #[inline]
+        fn read_line (self: &'_ mut Self, buf: &'_ mut String)
+          -> io::Result<usize>
+        {
+            B::read_line(&mut **self, buf)
+        }
+    }
+
+    impl<'frame, S : ?Sized + 'frame>
+        Seek
+    for
+        StackBox<'frame, S>
+    where
+        S : Seek,
+    {
+        #[inline]
+        fn seek (self: &'_ mut Self, pos: SeekFrom)
+          -> io::Result<u64>
+        {
+            S::seek(&mut **self, pos)
+        }
+    }
+
+    #[allow(deprecated)]
+    impl<'frame, E : ?Sized + 'frame>
+        Error
+    for
+        StackBox<'frame, E>
+    where
+        E : Error,
+    {
+        #[inline]
+        fn description (self: &'_ Self)
+          -> &'_ str
+        {
+            E::description(&**self)
+        }
+
+        #[inline]
+        fn cause (self: &'_ Self)
+          -> Option<&'_ dyn Error>
+        {
+            E::cause(&**self)
+        }
+
+        #[inline]
+        fn source (self: &'_ Self)
+          -> Option<&'_ (dyn Error + 'static)>
+        {
+            E::source(&**self)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::core::ops::Not as _;
+    use ::stackbox::prelude::*;
+
+    #[test]
+    fn fmt ()
+    {
+        stackbox!(let it = String::from("Hello"));
+        assert_eq!(format!("{}", it), "Hello");
+        assert_eq!(format!("{:?}", it), "\"Hello\"");
+        assert_eq!(format!("{:p}", it), format!("{:p}", &*it));
+    }
+
+    #[test]
+    fn cmp_and_hash ()
+    {
+        use ::std::collections::{BTreeSet, HashSet};
+
+        mk_slots!(a, b, c);
+        let a = a.stackbox(String::from("a"));
+        let b = b.stackbox(String::from("b"));
+        let c = c.stackbox(String::from("a"));
+        assert!(a < b);
+        assert_eq!(a, c);
+        assert_ne!(a, b);
+
+        let mut set = HashSet::new();
+        assert!(set.insert(a));
+        assert!(set.insert(b));
+        assert!(set.insert(c).not());
+        assert!(set.contains(&String::from("a")));
+
+        let set: BTreeSet<_> = set.into_iter().collect();
+        assert_eq!(
+            set.iter().map(|s| &***s).collect::<Vec<&str>>(),
+            ["a", "b"],
+        );
+    }
+
+    #[test]
+    fn iterators ()
+    {
+        stackbox!(let mut it = 0 .. 5);
+        assert_eq!(it.size_hint(), (5, Some(5)));
+        assert_eq!(it.len(), 5);
+        assert_eq!(it.next_back(), Some(4));
+        assert_eq!(it.nth(1), Some(1));
+        assert_eq!(it.collect::<Vec<_>>(), [2, 3]);
+
+        mk_slots!(s1, s2);
+        let mut it: StackBox<'_, dyn Iterator<Item = u32>> = if true {
+            stackbox!(s1, (0 .. 3).map(|x| x * 2))
+        } else {
+            stackbox!(s2, ::core::iter::empty())
+        };
+        assert_eq!(it.size_hint(), (3, Some(3)));
+        assert_eq!(it.next(), Some(0));
+        assert_eq!(it.collect::<Vec<_>>(), [2, 4]);
+
+        stackbox!(let mut it: StackBox<'_, dyn DoubleEndedIterator<Item = u8> + Send> = 0 .. 3);
+        assert_eq!(it.next_back(), Some(2));
+        assert_eq!(it.nth_back(1), Some(0));
+        assert_eq!(it.next(), None);
+
+        stackbox!(let it: StackBox<'_, dyn ExactSizeIterator<Item = u8>> = 0 .. 3);
+        assert_eq!(it.len(), 3);
+        assert_eq!(it.sum::<u8>(), 3);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn io ()
+    {
+        use ::std::io::{BufRead, Cursor, Read, Seek, SeekFrom, Write};
+
+        stackbox!(let mut reader: StackBox<'_, dyn BufRead> = &b"a\nbc"[..]);
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        assert_eq!(line, "a\n");
+        let mut rest = vec![];
+        reader.read_to_end(&mut rest).unwrap();
+        assert_eq!(rest, b"bc");
+
+        stackbox!(let mut cursor = Cursor::new(vec![]));
+        write!(cursor, "{}-{}", 4, 2).unwrap();
+        cursor.seek(SeekFrom::Start(1)).unwrap();
+        cursor.write_all(b"+").unwrap();
+        assert_eq!(StackBox::into_inner(cursor).into_inner(), b"4+2");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn error ()
+    {
+        use ::std::{error::Error, fmt, io};
+
+        #[derive(Debug)]
+        struct Wrapper(io::Error);
+
+        impl fmt::Display for Wrapper {
+            fn fmt (self: &'_ Self, f: &'_ mut fmt::Formatter<'_>)
+              -> fmt::Result
+            {
+                f.write_str("wrapper")
+            }
+        }
+
+        impl Error for Wrapper {
+            fn source (self: &'_ Self)
+              -> Option<&'_ (dyn Error + 'static)>
+            {
+                Some(&self.0)
+            }
+        }
+
+        let io_err = || io::Error::new(io::ErrorKind::Other, "inner");
+        stackbox!(let err: StackBox<'_, dyn Error> = Wrapper(io_err()));
+        assert_eq!(err.to_string(), "wrapper");
+        assert_eq!(err.source().unwrap().to_string(), "inner");
+    }
+}
@@ -0,0 +1,600 @@
+//! Owned string slices: `StackBox<'_, str>`, and the [`StackString`] builder.
+
+use super::*;
+
+use ::core::{fmt, ops::Not as _, str};
+
+use super::slice::IsArray;
+
+impl<'frame> StackBox<'frame, [u8]> {
+    /// Converts an owned byte slice into an owned string slice, in place,
+    /// after checking that it is valid UTF-8.
+    ///
+    /// On failure, the bytes are given back, alongside the [`str::Utf8Error`].
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ::stackbox::prelude::*;
+    ///
+    /// stackbox!(let bytes: StackBox<'_, [u8]> = *b"Hello, World!");
+    /// let s: StackBox<'_, str> = bytes.into_str().unwrap();
+    /// assert_eq!(&*s, "Hello, World!");
+    ///
+    /// stackbox!(let bytes: StackBox<'_, [u8]> = [0xff]);
+    /// let (_err, bytes) = bytes.into_str().unwrap_err();
+    /// assert_eq!(*bytes, [0xff]);
+    /// ```
+    #[inline]
+    pub
+    fn into_str (self: StackBox<'frame, [u8]>)
+      -> Result<
+            StackBox<'frame, str>,
+            (str::Utf8Error, StackBox<'frame, [u8]>),
+        >
+    {
+        match str::from_utf8(&self) {
+            | Ok(_) => Ok(unsafe {
+                // Safety: the bytes have just been checked.
+                self.into_str_unchecked()
+            }),
+            | Err(err) => Err((err, self)),
+        }
+    }
+
+    /// Converts an owned byte slice into an owned string slice, in place,
+    /// without checking that it is valid UTF-8.
+    ///
+    /// # Safety
+    ///
+    /// The bytes must be valid UTF-8.
+    #[inline]
+    pub
+    unsafe
+    fn into_str_unchecked (self: StackBox<'frame, [u8]>)
+      -> StackBox<'frame, str>
+    {
+        StackBox::from_raw(self.into_raw() as *mut str)
+    }
+}
+
+impl<'frame> StackBox<'frame, str> {
+    /// Converts an owned string slice into its owned bytes, in place.
+    #[inline]
+    pub
+    fn into_bytes (self: StackBox<'frame, str>)
+      -> StackBox<'frame, [u8]>
+    {
+        unsafe {
+            // Safety: same layout, and ownership is transferred.
+            StackBox::from_raw(self.into_raw() as *mut [u8])
+        }
+    }
+
+    /// Owned equivalent of [`str::split_at`].
+    ///
+    /// ### Panics
+    ///
+    /// If `mid` is not on a `char` boundary, or is out of bounds.
+    #[inline]
+    pub
+    fn stackbox_split_at (self: StackBox<'frame, str>, mid: usize)
+      -> (StackBox<'frame, str>, StackBox<'frame, str>)
+    {
+        assert!(self.is_char_boundary(mid),
+            "`stackbox_split_at`: `mid` is not on a `char` boundary",
+        );
+        let (hd, tl) = self.into_bytes().stackbox_split_at(mid);
+        unsafe {
+            // Safety: split on a `char` boundary.
+            (hd.into_str_unchecked(), tl.into_str_unchecked())
+        }
+    }
+
+    /// Owned equivalent of `str::split_once`: splits the string slice around
+    /// the first occurrence of `delimiter`, which is discarded.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ::stackbox::prelude::*;
+    ///
+    /// mk_slots!(storage);
+    /// let mut s = StackString::<[u8; 16]>::new_in(storage);
+    /// s.push_str("key=value");
+    /// let (key, value) = s.into_stackbox().stackbox_split_once("=").unwrap();
+    /// assert_eq!((&*key, &*value), ("key", "value"));
+    /// ```
+    #[inline]
+    pub
+    fn stackbox_split_once (
+        self: StackBox<'frame, str>,
+        delimiter: &'_ str,
+    ) -> Option<(StackBox<'frame, str>, StackBox<'frame, str>)>
+    {
+        let start = self.find(delimiter)?;
+        let (hd, tl) = self.stackbox_split_at(start);
+        let (_, tl) = tl.stackbox_split_at(delimiter.len());
+        Some((hd, tl))
+    }
+}
+
+impl<'frame> StackBox<'frame, str> {
+    /// Formats the given [`fmt::Arguments`] into the given [`Slot`] of a byte
+    /// array, failing with [`fmt::Error`] if they do not fit.
+    ///
+    /// This is what the [`stack_format!`][`crate::stack_format`] macro uses.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ::stackbox::prelude::*;
+    ///
+    /// let storage = &mut mk_slot::<[u8; 8]>();
+    /// let s = StackBox::format_in(storage, format_args!("{}-{}", 4, 2));
+    /// assert_eq!(&*s.unwrap(), "4-2");
+    /// ```
+    #[inline]
+    pub
+    fn format_in<Array : IsArray<'frame, Item = u8>> (
+        storage: &'frame mut Slot<Array>,
+        args: fmt::Arguments<'_>,
+    ) -> Result<StackBox<'frame, str>, fmt::Error>
+    {
+        let mut s = StackString::new_in(storage);
+        let mut writer = Bounded { s: &mut s, truncate: false, full: false };
+        // A `Display` impl may ignore the error from a write which did not
+        // fit, so `full` is checked too.
+        if fmt::Write::write_fmt(&mut writer, args).is_err() || writer.full {
+            return Err(fmt::Error);
+        }
+        Ok(s.into_stackbox())
+    }
+
+    /// Formats the given [`fmt::Arguments`] into the given [`Slot`] of a byte
+    /// array, truncating the output (at a `char` boundary) if it does not
+    /// fit.
+    #[inline]
+    pub
+    fn format_in_truncated<Array : IsArray<'frame, Item = u8>> (
+        storage: &'frame mut Slot<Array>,
+        args: fmt::Arguments<'_>,
+    ) -> StackBox<'frame, str>
+    {
+        let mut s = StackString::new_in(storage);
+        let _ = fmt::Write::write_fmt(
+            &mut Bounded { s: &mut s, truncate: true, full: false },
+            args,
+        );
+        s.into_stackbox()
+    }
+}
+
+/// A [`fmt::Write`]r into a [`StackString`] which, once its capacity is
+/// exceeded, ignores any further writes, so that its contents always are a
+/// prefix of the full output.
+struct Bounded<'s, 'frame, Array : IsArray<'frame, Item = u8>> {
+    s: &'s mut StackString<'frame, Array>,
+    /// Whether the write which does not fit is to be truncated (at a `char`
+    /// boundary) rather than discarded.
+    truncate: bool,
+    full: bool,
+}
+
+impl<'frame, Array : IsArray<'frame, Item = u8>>
+    fmt::Write
+for
+    Bounded<'_, 'frame, Array>
+{
+    fn write_str (self: &'_ mut Self, s: &'_ str)
+      -> fmt::Result
+    {
+        if self.full {
+            return Err(fmt::Error);
+        }
+        if self.s.try_push_str(s).is_ok() {
+            return Ok(());
+        }
+        self.full = true;
+        if self.truncate {
+            let mut end = self.s.capacity() - self.s.len();
+            while s.is_char_boundary(end).not() {
+                end -= 1;
+            }
+            self.s.push_str(&s[.. end]);
+        }
+        // Stop the formatting machinery: the output is now full.
+        Err(fmt::Error)
+    }
+}
+
+/// [`format!`]-like macro, which writes into a local buffer of the given
+/// capacity, to yield a [`StackBox`]`<'_, str>`.
+///
+/// The capacity must be [a supported array length][`StackBox::into_slice`].
+///
+///   - `stack_format!(let s = 256; "{}-{}", a, b)` binds `s` to a
+///     `Result<StackBox<'_, str>, ::core::fmt::Error>`, which is an error
+///     when the output does not fit;
+///
+///   - `stack_format!(let s = 256, truncate; "{}-{}", a, b)` binds `s` to a
+///     `StackBox<'_, str>`, whose contents are truncated (at a `char`
+///     boundary) when the output does not fit;
+///
+///   - `stack_format!(256; …)` and `stack_format!(256, truncate; …)` yield a
+///     temporary, _e.g._, to be fed to a function parameter.
+///
+/// For an explicit backing [`Slot`], see [`StackBox::format_in`].
+///
+/// ## Example
+///
+/// ```rust
+/// use ::stackbox::prelude::*;
+///
+/// fn log (msg: StackBox<'_, str>)
+/// {
+///     assert_eq!(&*msg, "Hello, World!");
+/// }
+///
+/// stack_format!(let msg = 16; "Hello, {}!", "World");
+/// log(msg.unwrap());
+///
+/// stack_format!(let msg = 8, truncate; "{:?}", ['a', 'b', 'c']);
+/// assert_eq!(&*msg, "['a', 'b");
+///
+/// log(stack_format!(64; "{}, {}!", "Hello", "World").unwrap());
+/// assert!(stack_format!(4; "{}", 12345).is_err());
+/// ```
+#[macro_export]
+macro_rules! stack_format {
+    (
+        let $var:ident = $N:expr, truncate;
+        $($args:tt)+
+    ) => (
+        let ref mut storage = $crate::mk_slot::<[u8; $N]>();
+        let $var = $crate::StackBox::format_in_truncated(
+            storage,
+            $crate::__::format_args!($($args)+),
+        );
+    );
+
+    (
+        let $var:ident = $N:expr;
+        $($args:tt)+
+    ) => (
+        let ref mut storage = $crate::mk_slot::<[u8; $N]>();
+        let $var = $crate::StackBox::format_in(
+            storage,
+            $crate::__::format_args!($($args)+),
+        );
+    );
+
+    (
+        $N:expr, truncate;
+        $($args:tt)+
+    ) => (
+        $crate::StackBox::format_in_truncated(
+            &mut $crate::mk_slot::<[u8; $N]>(),
+            $crate::__::format_args!($($args)+),
+        )
+    );
+
+    (
+        $N:expr;
+        $($args:tt)+
+    ) => (
+        $crate::StackBox::format_in(
+            &mut $crate::mk_slot::<[u8; $N]>(),
+            $crate::__::format_args!($($args)+),
+        )
+    );
+}
+
+/// A fixed-capacity `String`, whose (inline) backing storage is a [`Slot`]
+/// of a byte array: `Array = [u8; N]`.
+///
+/// It implements [`fmt::Write`], and can be frozen into an owned string
+/// slice, _i.e._, a [`StackBox`]`<'frame, str>`, through
+/// [`.into_stackbox()`][`StackString::into_stackbox`].
+///
+/// The same [requirements regarding `N`][`StackBox::into_slice`] apply.
+///
+/// ## Example
+///
+/// ```rust
+/// use ::core::fmt::Write;
+/// use ::stackbox::prelude::*;
+///
+/// mk_slots!(storage);
+/// let mut s = StackString::<[u8; 32]>::new_in(storage);
+/// write!(s, "{} + {} = {}", 1, 1, 2).unwrap();
+/// let s: StackBox<'_, str> = s.into_stackbox();
+/// assert_eq!(&*s, "1 + 1 = 2");
+/// ```
+pub
+struct StackString<'frame, Array : IsArray<'frame, Item = u8>> {
+    /// Invariant: valid UTF-8.
+    bytes: StackVec<'frame, Array>,
+}
+
+impl<'frame, Array : IsArray<'frame, Item = u8>> StackString<'frame, Array> {
+    /// Creates an empty `StackString`, backed by the given [`Slot`].
+    #[inline]
+    pub
+    fn new_in (storage: &'frame mut Slot<Array>)
+      -> StackString<'frame, Array>
+    {
+        StackString { bytes: StackVec::new_in(storage) }
+    }
+
+    /// The length in bytes.
+    #[inline]
+    pub
+    fn len (self: &'_ Self)
+      -> usize
+    {
+        self.bytes.len()
+    }
+
+    #[inline]
+    pub
+    fn is_empty (self: &'_ Self)
+      -> bool
+    {
+        self.bytes.is_empty()
+    }
+
+    /// The `N` in `Array = [u8; N]`.
+    #[inline]
+    pub
+    fn capacity (self: &'_ Self)
+      -> usize
+    {
+        self.bytes.capacity()
+    }
+
+    #[inline]
+    pub
+    fn as_str (self: &'_ Self)
+      -> &'_ str
+    {
+        unsafe {
+            // Safety: from the type invariant.
+            str::from_utf8_unchecked(&self.bytes)
+        }
+    }
+
+    #[inline]
+    pub
+    fn as_mut_str (self: &'_ mut Self)
+      -> &'_ mut str
+    {
+        unsafe {
+            // Safety: from the type invariant.
+            str::from_utf8_unchecked_mut(&mut self.bytes)
+        }
+    }
+
+    /// Appends a string slice, or gives it back if it does not fit.
+    #[inline]
+    pub
+    fn try_push_str<'s> (self: &'_ mut Self, s: &'s str)
+      -> Result<(), &'s str>
+    {
+        if s.len() > self.capacity() - self.len() {
+            return Err(s);
+        }
+        self.bytes.extend(s.bytes());
+        Ok(())
+    }
+
+    /// Appends a string slice.
+    ///
+    /// ### Panics
+    ///
+    /// If it does not fit.
+    #[inline]
+    pub
+    fn push_str (self: &'_ mut Self, s: &'_ str)
+    {
+        if self.try_push_str(s).is_err() {
+            panic!("`StackString::push_str`: capacity ({}) exceeded",
+                self.capacity(),
+            );
+        }
+    }
+
+    /// Appends a `char`, or gives it back if it does not fit.
+    #[inline]
+    pub
+    fn try_push (self: &'_ mut Self, c: char)
+      -> Result<(), char>
+    {
+        self.try_push_str(c.encode_utf8(&mut [0; 4]))
+            .map_err(|_| c)
+    }
+
+    /// Appends a `char`.
+    ///
+    /// ### Panics
+    ///
+    /// If it does not fit.
+    #[inline]
+    pub
+    fn push (self: &'_ mut Self, c: char)
+    {
+        self.push_str(c.encode_utf8(&mut [0; 4]))
+    }
+
+    /// Removes the last `char` and returns it, if any.
+    #[inline]
+    pub
+    fn pop (self: &'_ mut Self)
+      -> Option<char>
+    {
+        let c = self.as_str().chars().next_back()?;
+        for _ in 0 .. c.len_utf8() {
+            self.bytes.pop();
+        }
+        Some(c)
+    }
+
+    /// Freezes the `StackString` into an owned string slice.
+    #[inline]
+    pub
+    fn into_stackbox (self: StackString<'frame, Array>)
+      -> StackBox<'frame, str>
+    {
+        unsafe {
+            // Safety: from the type invariant.
+            self.bytes.into_stackbox().into_str_unchecked()
+        }
+    }
+}
+
+impl<'frame, Array : IsArray<'frame, Item = u8>>
+    ::core::ops::Deref
+for
+    StackString<'frame, Array>
+{
+    type Target = str;
+
+    #[inline]
+    fn deref (self: &'_ StackString<'frame, Array>)
+      -> &'_ str
+    {
+        self.as_str()
+    }
+}
+
+impl<'frame, Array : IsArray<'frame, Item = u8>>
+    ::core::ops::DerefMut
+for
+    StackString<'frame, Array>
+{
+    #[inline]
+    fn deref_mut (self: &'_ mut StackString<'frame, Array>)
+      -> &'_ mut str
+    {
+        self.as_mut_str()
+    }
+}
+
+/// A [`fmt::Error`] is returned when the capacity is exceeded.
+impl<'frame, Array : IsArray<'frame, Item = u8>>
+    fmt::Write
+for
+    StackString<'frame, Array>
+{
+    #[inline]
+    fn write_str (self: &'_ mut StackString<'frame, Array>, s: &'_ str)
+      -> fmt::Result
+    {
+        self.try_push_str(s).map_err(|_| fmt::Error)
+    }
+}
+
+impl<'frame, Array : IsArray<'frame, Item = u8>>
+    fmt::Debug
+for
+    StackString<'frame, Array>
+{
+    fn fmt (self: &'_ Self, f: &'_ mut fmt::Formatter<'_>)
+      -> fmt::Result
+    {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl<'frame, Array : IsArray<'frame, Item = u8>>
+    fmt::Display
+for
+    StackString<'frame, Array>
+{
+    fn fmt (self: &'_ Self, f: &'_ mut fmt::Formatter<'_>)
+      -> fmt::Result
+    {
+        fmt::Display::fmt(self.as_str(), f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::core::fmt::Write;
+    use ::stackbox::prelude::*;
+
+    #[test]
+    fn stack_string ()
+    {
+        mk_slots!(storage);
+        let mut s = StackString::<[u8; 8]>::new_in(storage);
+        s.push_str("héllo");
+        assert_eq!(s.len(), 6);
+        assert_eq!(s.try_push_str("...").unwrap_err(), "...");
+        assert!(write!(s, "!!!").is_err());
+        assert_eq!(s.try_push('€'), Err('€'));
+        s.push('!');
+        assert_eq!(s.pop(), Some('!'));
+        assert_eq!(s.pop(), Some('o'));
+        assert_eq!(format!("{:?} {}", s, s), r#""héll" héll"#);
+        let s = s.into_stackbox();
+        let (hd, tl) = s.stackbox_split_at(3);
+        assert_eq!((&*hd, &*tl), ("hé", "ll"));
+        assert!(tl.stackbox_split_once("x").is_none());
+    }
+
+    #[test]
+    fn stack_format ()
+    {
+        stack_format!(let s = 4; "{}", 1234);
+        assert_eq!(&*s.unwrap(), "1234");
+        stack_format!(let s = 4; "{}", 12345);
+        assert!(s.is_err());
+        stack_format!(let s = 4, truncate; "{}{}", "a", "€");
+        assert_eq!(&*s, "a€");
+        stack_format!(let s = 3, truncate; "{}{}{}", "a", "€", "b");
+        assert_eq!(&*s, "a");
+        assert_eq!(&*stack_format!(2, truncate; "{:?}", ()), "()");
+    }
+
+    #[test]
+    fn stack_format_ignored_errors ()
+    {
+        /// A `Display` impl which (wrongly) ignores write errors.
+        struct Sloppy;
+        impl ::core::fmt::Display for Sloppy {
+            fn fmt (self: &'_ Self, f: &'_ mut ::core::fmt::Formatter<'_>)
+              -> ::core::fmt::Result
+            {
+                let _ = f.write_str("abc");
+                let _ = f.write_str("d€");
+                let _ = f.write_str("i");
+                Ok(())
+            }
+        }
+
+        // The output is a prefix of `"abcd€i"`.
+        stack_format!(let s = 6, truncate; "{}", Sloppy);
+        assert_eq!(&*s, "abcd");
+        stack_format!(let s = 5; "{}", Sloppy);
+        assert!(s.is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn split_at_non_char_boundary ()
+    {
+        stackbox!(let bytes: StackBox<'_, [u8]> = *b"\xc3\xa9");
+        let _ = bytes.into_str().unwrap().stackbox_split_at(1);
+    }
+
+    #[test]
+    fn bytes_roundtrip ()
+    {
+        stackbox!(let bytes: StackBox<'_, [u8]> = *b"a=b=c");
+        let s = bytes.into_str().unwrap();
+        let (a, bc) = s.stackbox_split_once("=").unwrap();
+        assert_eq!(&*a, "a");
+        assert_eq!(*bc.into_bytes(), *b"b=c");
+    }
+}
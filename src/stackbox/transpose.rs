@@ -0,0 +1,98 @@
+//! Owned enum projections: `StackBox<Option<T>> → Option<StackBox<T>>`, and
+//! `StackBox<Result<T, E>> → Result<StackBox<T>, StackBox<E>>`.
+
+use super::*;
+
+impl<'frame, T : 'frame> StackBox<'frame, Option<T>> {
+    /// Projects the `StackBox` onto the payload of the `Option`, in place.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ::stackbox::prelude::*;
+    ///
+    /// stackbox!(let it = Some([42_u8; 1 << 16]));
+    /// let it: StackBox<'_, [u8; 1 << 16]> = it.transpose().unwrap();
+    /// assert_eq!(it[0], 42);
+    /// ```
+    #[inline]
+    pub
+    fn transpose (self: StackBox<'frame, Option<T>>)
+      -> Option<StackBox<'frame, T>>
+    {
+        let ptr: *mut Option<T> = self.into_raw();
+        match unsafe { &mut *ptr } {
+            | &mut Some(ref mut it) => Some(unsafe {
+                // Safety: ownership of the payload is transferred.
+                StackBox::from_raw(it)
+            }),
+            | &mut None => None,
+        }
+    }
+}
+
+impl<'frame, T : 'frame, E : 'frame> StackBox<'frame, Result<T, E>> {
+    /// Projects the `StackBox` onto the payload of the `Result`, in place.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ::stackbox::prelude::*;
+    ///
+    /// stackbox!(let it = Err::<[u8; 1 << 16], _>(String::from("error")));
+    /// let err: StackBox<'_, String> = match it.transpose() {
+    ///     Ok(_) => unreachable!(),
+    ///     Err(err) => err,
+    /// };
+    /// assert_eq!(*err, "error");
+    /// ```
+    #[inline]
+    pub
+    fn transpose (self: StackBox<'frame, Result<T, E>>)
+      -> Result<StackBox<'frame, T>, StackBox<'frame, E>>
+    {
+        let ptr: *mut Result<T, E> = self.into_raw();
+        unsafe {
+            // Safety: ownership of the payload is transferred.
+            match &mut *ptr {
+                | &mut Ok(ref mut it) => Ok(StackBox::from_raw(it)),
+                | &mut Err(ref mut err) => Err(StackBox::from_raw(err)),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::stackbox::prelude::*;
+
+    #[test]
+    fn test_drops ()
+    {
+        let rc = ::std::rc::Rc::new(());
+        let count = || ::std::rc::Rc::strong_count(&rc);
+        let rc = || rc.clone();
+
+        stackbox!(let it = Some(rc()));
+        assert_eq!(count(), 2);
+        let it = it.transpose().unwrap();
+        assert_eq!(count(), 2);
+        drop(it);
+        assert_eq!(count(), 1);
+
+        stackbox!(let it = None::<::std::rc::Rc<()>>);
+        assert!(it.transpose().is_none());
+
+        stackbox!(let it = Ok::<_, ::std::rc::Rc<()>>(rc()));
+        assert_eq!(count(), 2);
+        let it = it.transpose().unwrap();
+        drop(it);
+        assert_eq!(count(), 1);
+
+        stackbox!(let it = Err::<(), _>(rc()));
+        assert_eq!(count(), 2);
+        let it = it.transpose().unwrap_err();
+        drop(it);
+        assert_eq!(count(), 1);
+    }
+}
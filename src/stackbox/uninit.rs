@@ -0,0 +1,215 @@
+//! Owned boxes of uninitialized memory: `StackBox<'_, MaybeUninit<T>>` and
+//! `StackBox<'_, [MaybeUninit<T>]>`.
+
+use super::*;
+
+use ::core::mem::MaybeUninit;
+
+use super::slice::IsArray;
+
+impl<'frame, T : 'frame> StackBox<'frame, MaybeUninit<T>> {
+    /// Initializes the pointee with the given `value`, in place.
+    #[inline]
+    pub
+    fn write (self: StackBox<'frame, MaybeUninit<T>>, value: T)
+      -> StackBox<'frame, T>
+    {
+        let ptr: *mut T = self.into_raw().cast();
+        unsafe {
+            // Safety: the pointee is initialized right before its ownership is
+            // transferred.
+            ptr.write(value);
+            StackBox::from_raw(ptr)
+        }
+    }
+
+    /// # Safety
+    ///
+    /// The pointee must have been initialized.
+    #[inline]
+    pub
+    unsafe
+    fn assume_init (self: StackBox<'frame, MaybeUninit<T>>)
+      -> StackBox<'frame, T>
+    {
+        StackBox::from_raw(self.into_raw().cast())
+    }
+}
+
+impl<'frame, Array : IsArray<'frame>> StackBox<'frame, MaybeUninit<Array>> {
+    /// Views an uninitialized `[T; N]` as a slice of `N` uninitialized `T`s.
+    ///
+    /// The same [requirements regarding `N`][`StackBox::into_slice`] apply.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ::stackbox::prelude::*;
+    ///
+    /// let slot = &mut mk_slot::<[u64; 8]>();
+    /// let squares: StackBox<'_, [u64]> =
+    ///     slot.uninit_stackbox()
+    ///         .into_uninit_slice()
+    ///         .init_from_fn(|i| (i * i) as u64)
+    /// ;
+    /// assert_eq!(squares[7], 49);
+    /// ```
+    #[inline]
+    pub
+    fn into_uninit_slice (self: StackBox<'frame, MaybeUninit<Array>>)
+      -> StackBox<'frame, [MaybeUninit<Array::Item>]>
+    {
+        let ptr: *mut MaybeUninit<Array::Item> = self.into_raw().cast();
+        unsafe {
+            // Safety: `MaybeUninit<[T; N]>` has the layout of `N` consecutive
+            // `MaybeUninit<T>`s, and ownership is transferred.
+            StackBox::from_raw(
+                ::core::ptr::slice_from_raw_parts_mut(ptr, Array::LEN)
+            )
+        }
+    }
+}
+
+impl<'frame, T : 'frame> StackBox<'frame, [MaybeUninit<T>]> {
+    /// # Safety
+    ///
+    /// All the items must have been initialized.
+    #[inline]
+    pub
+    unsafe
+    fn assume_init (self: StackBox<'frame, [MaybeUninit<T>]>)
+      -> StackBox<'frame, [T]>
+    {
+        StackBox::from_raw(self.into_raw() as *mut [T])
+    }
+
+    /// Initializes each item with `f(index)`, in order.
+    ///
+    /// Should `f` panic, the already initialized items are dropped.
+    pub
+    fn init_from_fn (
+        self: StackBox<'frame, [MaybeUninit<T>]>,
+        mut f: impl FnMut(usize) -> T,
+    ) -> StackBox<'frame, [T]>
+    {
+        let len = self.len();
+        let base: *mut T = self.into_raw() as *mut MaybeUninit<T> as _;
+        let mut guard = InitGuard { base, initialized: 0 };
+        while guard.initialized < len {
+            let value = f(guard.initialized);
+            unsafe {
+                // Safety: in bounds.
+                guard.push(value);
+            }
+        }
+        unsafe {
+            // Safety: the prefix is (now) the whole slice.
+            guard.into_stackbox()
+        }
+    }
+}
+
+/// Drops the `initialized` prefix on unwind.
+pub(in super)
+struct InitGuard<T> {
+    pub(in super)
+    base: *mut T,
+    pub(in super)
+    initialized: usize,
+}
+
+impl<T> InitGuard<T> {
+    /// # Safety
+    ///
+    /// `base + initialized` must be in bounds of the buffer.
+    #[inline]
+    pub(in super)
+    unsafe
+    fn push (self: &'_ mut InitGuard<T>, value: T)
+    {
+        self.base.add(self.initialized).write(value);
+        self.initialized += 1;
+    }
+
+    /// # Safety
+    ///
+    /// The buffer must outlive `'frame`.
+    #[inline]
+    pub(in super)
+    unsafe
+    fn into_stackbox<'frame> (self: InitGuard<T>)
+      -> StackBox<'frame, [T]>
+    {
+        let this = ManuallyDrop::new(self);
+        StackBox::from_raw(::core::ptr::slice_from_raw_parts_mut(
+            this.base,
+            this.initialized,
+        ))
+    }
+}
+
+impl<T> Drop for InitGuard<T> {
+    fn drop (self: &'_ mut Self)
+    {
+        unsafe {
+            ::core::ptr::drop_in_place::<[T]>(
+                ::core::ptr::slice_from_raw_parts_mut(
+                    self.base,
+                    self.initialized,
+                )
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::core::mem::MaybeUninit;
+    use ::stackbox::prelude::*;
+
+    #[test]
+    fn test_drops ()
+    {
+        let rc = ::std::rc::Rc::new(());
+        let count = || ::std::rc::Rc::strong_count(&rc);
+        let rc = || rc.clone();
+
+        mk_slots!(slot);
+        let it = slot.uninit_stackbox().write(rc());
+        assert_eq!(count(), 2);
+        drop(it);
+        assert_eq!(count(), 1);
+
+        mk_slots!(slot);
+        let uninit: StackBox<'_, MaybeUninit<[_; 4]>> = slot.uninit_stackbox();
+        let caught = ::std::panic::catch_unwind(
+            ::std::panic::AssertUnwindSafe(|| {
+                uninit.into_uninit_slice().init_from_fn(|i| {
+                    assert!(i < 2);
+                    rc()
+                })
+            })
+        );
+        assert!(caught.is_err());
+        assert_eq!(count(), 1);
+
+        mk_slots!(slot);
+        let uninit: StackBox<'_, MaybeUninit<[_; 4]>> = slot.uninit_stackbox();
+        let slice = uninit.into_uninit_slice().init_from_fn(|_| rc());
+        assert_eq!(count(), 5);
+        drop(slice);
+        assert_eq!(count(), 1);
+    }
+
+    #[test]
+    fn assume_init ()
+    {
+        mk_slots!(slot);
+        let mut uninit: StackBox<'_, MaybeUninit<[u8; 2]>> = slot.uninit_stackbox();
+        *uninit = MaybeUninit::new([4, 2]);
+        let mut slice = uninit.into_uninit_slice();
+        slice[0] = MaybeUninit::new(0);
+        let slice: StackBox<'_, [u8]> = unsafe { slice.assume_init() };
+        assert_eq!(*slice, [0, 2]);
+    }
+}
@@ -0,0 +1,271 @@
+//! [`Slot`]s keeping track of their occupancy, so as to recover the values of
+//! leaked [`StackBox`]es.
+
+use crate::prelude::*;
+
+use ::core::{
+    fmt,
+    mem::ManuallyDrop,
+    ops::{Deref, DerefMut},
+};
+
+use crate::Slot;
+
+/// A [`Slot`] which records whether it currently holds a live value.
+///
+/// A plain [`Slot`] has no way to know whether the [`StackBox`] it handed out
+/// has been dropped or leaked (_e.g._, through [`mem::forget`]): reusing it
+/// just overwrites the previous contents, and dropping it never runs the
+/// leaked value's destructor.
+///
+/// A `TrackedSlot`, on the other hand, hands out [`TrackedBox`]es, which
+/// clear the slot's occupancy flag when dropped. If one of them is leaked,
+/// then, once the borrow on the slot ends:
+///
+///   - [`.is_occupied()`][`TrackedSlot::is_occupied`] reports it,
+///
+///   - [`.take()`][`TrackedSlot::take`] gives the leaked value back,
+///
+///   - and dropping the slot drops the leaked value.
+///
+/// Initializing an occupied slot triggers a debug assertion (and, in release
+/// mode, drops the previous value).
+///
+/// [`mem::forget`]: `::core::mem::forget`
+///
+/// ## Example
+///
+/// ```rust
+/// use ::stackbox::prelude::*;
+/// use ::stackbox::TrackedSlot;
+///
+/// let mut slot = TrackedSlot::VACANT;
+/// let boxed = slot.stackbox(String::from("Hello"));
+/// assert_eq!(*boxed, "Hello");
+/// ::core::mem::forget(boxed);
+///
+/// assert!(slot.is_occupied());
+/// assert_eq!(slot.take().unwrap(), "Hello");
+/// assert!(!slot.is_occupied());
+/// ```
+pub
+struct TrackedSlot<T> {
+    slot: Slot<T>,
+    /// Whether `slot` holds a value, be it owned by a live `TrackedBox`, or
+    /// leaked.
+    occupied: bool,
+}
+
+impl<T> TrackedSlot<T> {
+    /// A vacant slot.
+    pub
+    const VACANT: Self = TrackedSlot {
+        slot: Slot::VACANT,
+        occupied: false,
+    };
+
+    /// Whether the slot holds a value, which can only be the case after a
+    /// [`TrackedBox`] has been leaked, or while it is alive.
+    #[inline]
+    pub
+    fn is_occupied (self: &'_ TrackedSlot<T>)
+      -> bool
+    {
+        self.occupied
+    }
+
+    /// Moves `value` into the slot, and yields an owning [`TrackedBox`].
+    ///
+    /// Only the `TrackedBox` itself is tracked: once converted into a plain
+    /// [`StackBox`] with [`.into_stackbox()`][`TrackedBox::into_stackbox`],
+    /// the slot is considered vacant, and should that `StackBox` be leaked,
+    /// [`.take()`][`TrackedSlot::take`] can no longer recover its value.
+    ///
+    /// ### Panics
+    ///
+    /// With debug assertions enabled, if the slot is occupied.
+    #[inline]
+    pub
+    fn stackbox<'frame> (self: &'frame mut TrackedSlot<T>, value: T)
+      -> TrackedBox<'frame, T>
+    where
+        T : 'frame,
+    {
+        let leaked = self.take();
+        debug_assert!(leaked.is_none(),
+            "`TrackedSlot::stackbox`: slot initialized twice",
+        );
+        drop(leaked);
+        let TrackedSlot { slot, occupied } = self;
+        *occupied = true;
+        TrackedBox {
+            boxed: ManuallyDrop::new(slot.stackbox(value)),
+            occupied,
+        }
+    }
+
+    /// Takes the value held by the slot, if any: that is, the value of a
+    /// leaked [`TrackedBox`].
+    #[inline]
+    pub
+    fn take (self: &'_ mut TrackedSlot<T>)
+      -> Option<T>
+    {
+        if self.occupied {
+            self.occupied = false;
+            Some(unsafe {
+                // Safety: the slot was occupied, and it is no longer.
+                self.slot.place.as_ptr().read()
+            })
+        } else {
+            None
+        }
+    }
+}
+
+impl<T> Default for TrackedSlot<T> {
+    #[inline]
+    fn default ()
+      -> TrackedSlot<T>
+    {
+        TrackedSlot::VACANT
+    }
+}
+
+impl<T> Drop for TrackedSlot<T> {
+    #[inline]
+    fn drop (self: &'_ mut TrackedSlot<T>)
+    {
+        drop(self.take());
+    }
+}
+
+/// A [`StackBox`] obtained from a [`TrackedSlot`], which marks the slot as
+/// vacant when dropped.
+pub
+struct TrackedBox<'frame, T : 'frame> {
+    boxed: ManuallyDrop<StackBox<'frame, T>>,
+    occupied: &'frame mut bool,
+}
+
+impl<'frame, T : 'frame> TrackedBox<'frame, T> {
+    /// Stops tracking the value, yielding a plain [`StackBox`] owning it.
+    ///
+    /// The slot is then considered vacant: should the returned `StackBox` be
+    /// leaked, so will its pointee, which [`TrackedSlot::take`] can then no
+    /// longer recover (nor will dropping the slot drop it).
+    #[inline]
+    pub
+    fn into_stackbox (self: TrackedBox<'frame, T>)
+      -> StackBox<'frame, T>
+    {
+        let mut this = ManuallyDrop::new(self);
+        *this.occupied = false;
+        unsafe {
+            // Safety: `this.boxed` is not used afterwards.
+            ManuallyDrop::take(&mut this.boxed)
+        }
+    }
+
+    /// Moves the value out of the slot, marking it as vacant.
+    #[inline]
+    pub
+    fn into_inner (self: TrackedBox<'frame, T>)
+      -> T
+    {
+        self.into_stackbox().into_inner()
+    }
+}
+
+impl<'frame, T : 'frame> Deref for TrackedBox<'frame, T> {
+    type Target = T;
+
+    #[inline]
+    fn deref (self: &'_ TrackedBox<'frame, T>)
+      -> &'_ T
+    {
+        &**self.boxed
+    }
+}
+
+impl<'frame, T : 'frame> DerefMut for TrackedBox<'frame, T> {
+    #[inline]
+    fn deref_mut (self: &'_ mut TrackedBox<'frame, T>)
+      -> &'_ mut T
+    {
+        &mut **self.boxed
+    }
+}
+
+impl<'frame, T : 'frame>
+    fmt::Debug
+for
+    TrackedBox<'frame, T>
+where
+    T : fmt::Debug,
+{
+    fn fmt (self: &'_ Self, f: &'_ mut fmt::Formatter<'_>)
+      -> fmt::Result
+    {
+        T::fmt(self, f)
+    }
+}
+
+impl<'frame, T : 'frame> Drop for TrackedBox<'frame, T> {
+    #[inline]
+    fn drop (self: &'_ mut TrackedBox<'frame, T>)
+    {
+        // Mark the slot as vacant first, so that a panicking destructor does
+        // not lead to a double drop.
+        *self.occupied = false;
+        unsafe {
+            // Safety: `self.boxed` is not used afterwards.
+            ManuallyDrop::drop(&mut self.boxed)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::std::rc::Rc;
+    use ::stackbox::TrackedSlot;
+
+    #[test]
+    fn test_drops ()
+    {
+        let rc = Rc::new(());
+        let count = || Rc::strong_count(&rc);
+        let rc = || rc.clone();
+
+        let mut slot = TrackedSlot::VACANT;
+        drop(slot.stackbox(rc()));
+        assert!(!slot.is_occupied());
+        assert_eq!(count(), 1);
+
+        ::core::mem::forget(slot.stackbox(rc()));
+        assert!(slot.is_occupied());
+        assert_eq!(count(), 2);
+        drop(slot);
+        assert_eq!(count(), 1);
+
+        let mut slot = TrackedSlot::VACANT;
+        let it = slot.stackbox(rc()).into_stackbox();
+        ::core::mem::forget(it);
+        assert!(!slot.is_occupied());
+        assert!(slot.take().is_none());
+
+        let mut slot = TrackedSlot::default();
+        assert_eq!(Rc::strong_count(&slot.stackbox(rc()).into_inner()), 3);
+        assert!(!slot.is_occupied());
+    }
+
+    #[test]
+    #[cfg_attr(debug_assertions, should_panic(expected = "initialized twice"))]
+    fn initialized_twice ()
+    {
+        let mut slot = TrackedSlot::VACANT;
+        ::core::mem::forget(slot.stackbox(String::from("leaked")));
+        let it = slot.stackbox(String::from("new"));
+        assert_eq!(*it, "new");
+    }
+}